@@ -0,0 +1,48 @@
+//! With the `embed-inputs` feature, compile every `src/inputs/<year>/<day>.txt` into the library.
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/inputs");
+
+    let mut entries = Vec::new();
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+        let inputs = manifest_dir.join("src").join("inputs");
+        for year in fs::read_dir(&inputs).into_iter().flatten().flatten() {
+            let year_number = match year.file_name().to_str().map(str::parse::<u16>) {
+                Some(Ok(year_number)) => year_number,
+                _ => continue,
+            };
+            println!("cargo:rerun-if-changed={}", year.path().display());
+            for day in fs::read_dir(year.path()).into_iter().flatten().flatten() {
+                let path = day.path();
+                let day_number = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse::<u8>().ok());
+                match (path.extension(), day_number) {
+                    (Some(extension), Some(day_number)) if extension == "txt" => {
+                        entries.push((year_number, day_number, path))
+                    }
+                    _ => continue,
+                }
+            }
+        }
+    }
+    entries.sort();
+
+    let mut generated = String::from("static EMBEDDED: &[(u16, u8, &str)] = &[\n");
+    for (year, day, path) in entries {
+        writeln!(
+            generated,
+            "    ({}, {}, include_str!({:?})),",
+            year, day, path
+        )
+        .unwrap();
+    }
+    generated.push_str("];\n");
+
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out, generated).unwrap();
+}
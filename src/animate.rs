@@ -0,0 +1,244 @@
+//! Simulations played back a step at a time, built on [`crate::visualize`].
+//!
+//! A [`Recorder`] draws the state after each step, and the resulting [`Animation`]
+//! can be written as an animated GIF, a numbered sequence of PNG frames, or replayed in the terminal.
+//! Days list what they can record in [`RECORDINGS`], for `aoc animate`.
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{bail, ensure, Context};
+
+use crate::{
+    visualize::{Image, MAX_PIXELS},
+    y2021::{giant_squid, lanternfish},
+};
+
+/// Across every frame, since they're all kept in memory until the animation is written
+pub const MAX_TOTAL_PIXELS: usize = 4 * MAX_PIXELS;
+
+fn pixels(frame: &Image) -> usize {
+    frame.width() * frame.height()
+}
+
+/// Draws the state of a simulation after each step
+pub struct Recorder<S> {
+    draw: fn(&S) -> Image,
+    frames: Vec<Image>,
+    pixels: usize,
+}
+
+impl<S> Recorder<S> {
+    pub fn new(draw: fn(&S) -> Image) -> Self {
+        Self {
+            draw,
+            frames: Vec::new(),
+            pixels: 0,
+        }
+    }
+
+    /// Fails once the frames add up to more than [`MAX_TOTAL_PIXELS`]
+    pub fn record(&mut self, state: &S) -> anyhow::Result<()> {
+        let frame = (self.draw)(state);
+        self.pixels += pixels(&frame);
+        ensure!(
+            self.pixels <= MAX_TOTAL_PIXELS,
+            "Too big to record after {} frames",
+            self.frames.len()
+        );
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    pub fn finish(self) -> Animation {
+        Animation {
+            frames: self.frames,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    frames: Vec<Image>,
+}
+
+impl Animation {
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// See [`Image::scale`]. Fails if the frames would add up to more than [`MAX_TOTAL_PIXELS`]
+    pub fn scale(&self, factor: usize) -> anyhow::Result<Self> {
+        let total = self.frames.iter().try_fold(0_usize, |total, frame| {
+            total.checked_add(pixels(frame).checked_mul(factor)?.checked_mul(factor)?)
+        });
+        ensure!(
+            total.is_some_and(|total| total <= MAX_TOTAL_PIXELS),
+            "{} frames scaled by {} are too big to record",
+            self.frames.len(),
+            factor
+        );
+        Ok(Self {
+            frames: self
+                .frames
+                .iter()
+                .map(|frame| frame.scale(factor))
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+
+    /// Loops forever, showing each frame for `delay`, to the nearest hundredth of a second.
+    /// Every frame must be the same size.
+    pub fn write_gif(&self, writer: impl Write, delay: Duration) -> anyhow::Result<()> {
+        let Some(first) = self.frames.first() else {
+            bail!("There are no frames to write")
+        };
+        let (width, height) = (first.width(), first.height());
+        if let Some(index) = self
+            .frames
+            .iter()
+            .position(|frame| (frame.width(), frame.height()) != (width, height))
+        {
+            bail!(
+                "Frame {} isn't {} by {} like the first",
+                index,
+                width,
+                height
+            )
+        }
+        let (width, height) = (
+            width.try_into().context("Too wide for a GIF")?,
+            height.try_into().context("Too tall for a GIF")?,
+        );
+        let mut encoder = gif::Encoder::new(writer, width, height, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for frame in &self.frames {
+            // Quantizing is slow, and puzzle pictures don't use many colours anyway
+            let mut gif_frame = gif::Frame::from_rgb_speed(width, height, &frame.bytes(), 10);
+            gif_frame.delay = (delay.as_millis() / 10).try_into().unwrap_or(u16::MAX);
+            encoder.write_frame(&gif_frame)?;
+        }
+        Ok(())
+    }
+
+    /// `frame-0000.png` and so on, numbered from 0. Returns the paths written
+    pub fn write_frames(&self, directory: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        fs::create_dir_all(directory)
+            .with_context(|| format!("Couldn't create {}", directory.display()))?;
+        let digits = self.frames.len().saturating_sub(1).to_string().len().max(4);
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let path = directory.join(format!("frame-{:0digits$}.png", index, digits = digits));
+                frame.save(&path)?;
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// As GIF or a directory of PNG frames, depending on whether `path` ends with `.gif`
+    pub fn save(&self, path: &Path, delay: Duration) -> anyhow::Result<()> {
+        match path.extension().is_some_and(|extension| extension == "gif") {
+            true => {
+                let file = File::create(path)
+                    .with_context(|| format!("Couldn't create {}", path.display()))?;
+                let mut writer = BufWriter::new(file);
+                self.write_gif(&mut writer, delay)?;
+                writer
+                    .flush()
+                    .with_context(|| format!("Couldn't write {}", path.display()))
+            }
+            false => self.write_frames(path).map(drop),
+        }
+    }
+
+    /// Draw each frame over the last as terminal art, waiting `delay` in between
+    pub fn replay(&self, mut writer: impl Write, delay: Duration) -> anyhow::Result<()> {
+        // Clear the screen once, then just go back to the top so it doesn't flicker
+        write!(writer, "\x1b[2J")?;
+        for (index, frame) in self.frames.iter().enumerate() {
+            if index > 0 {
+                thread::sleep(delay);
+            }
+            write!(writer, "\x1b[H{}", frame)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// A simulation a day can record from its input
+#[derive(Debug, Clone, Copy)]
+pub struct Recording {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    pub record: fn(&str) -> anyhow::Result<Animation>,
+}
+
+static RECORDINGS: &[&[Recording]] = &[&giant_squid::RECORDINGS, &lanternfish::RECORDINGS];
+
+pub fn all() -> impl Iterator<Item = &'static Recording> {
+    RECORDINGS.iter().flat_map(|recordings| recordings.iter())
+}
+
+/// Every recording of a day, in the order they were listed
+pub fn of(year: u16, day: u8) -> impl Iterator<Item = &'static Recording> {
+    all().filter(move |recording| recording.year == year && recording.day == day)
+}
+
+#[test]
+fn writes_animations() {
+    use crate::visualize::Rgb;
+
+    let mut recorder = Recorder::new(|shade: &u8| Image::new(3, 2, Rgb(*shade, *shade, *shade)));
+    for shade in [0, 128, 255] {
+        recorder.record(&shade).unwrap();
+    }
+    let animation = recorder.finish().scale(2).unwrap();
+    assert_eq!(animation.frames().len(), 3);
+    // Each frame is small enough, but not all of them together
+    assert!(animation.scale(1000).is_err());
+
+    let mut gif = Vec::new();
+    animation
+        .write_gif(&mut gif, Duration::from_millis(50))
+        .unwrap();
+    let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (6, 4));
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    assert_eq!(delays, [5, 5, 5]);
+
+    let directory = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+    let paths = animation.write_frames(&directory).unwrap();
+    assert_eq!(paths.last(), Some(&directory.join("frame-0002.png")));
+    fs::remove_dir_all(directory).unwrap();
+
+    let mut replay = Vec::new();
+    animation.replay(&mut replay, Duration::ZERO).unwrap();
+    assert_eq!(
+        String::from_utf8(replay).unwrap().matches("\x1b[H").count(),
+        3
+    );
+
+    let mut uneven = animation.clone();
+    uneven.frames.push(Image::new(1, 1, Rgb::BLACK));
+    assert!(uneven.write_gif(Vec::new(), Duration::ZERO).is_err());
+}
+
+#[test]
+fn records_every_recording() {
+    for recording in all() {
+        let input = crate::input::puzzle_input(recording.year, recording.day);
+        let animation = (recording.record)(&input).unwrap();
+        assert!(animation.frames().len() > 1, "{}", recording.name);
+    }
+}
@@ -0,0 +1,320 @@
+//! Timing solvers outside of `cargo bench`, so the numbers can be reused.
+//! Runs are kept in a history keyed by git commit, so a slower solver can be caught.
+use std::{
+    fs,
+    hint::black_box,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use anyhow::{ensure, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::{input::InputSource, solution::Solver};
+
+/// What's being timed. Parts are timed from already parsed input
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, derive_more::Display,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    #[display(fmt = "parse")]
+    Parse,
+    #[display(fmt = "part 1")]
+    Part1,
+    #[display(fmt = "part 2")]
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+}
+
+/// Run a phase `runs` times, returning the median runtime
+pub fn median_runtime(
+    solver: &dyn Solver,
+    phase: Phase,
+    input: &str,
+    runs: usize,
+) -> anyhow::Result<Duration> {
+    ensure!(runs > 0, "Must run at least once");
+    let parsed = solver.parse(input)?;
+    let mut timings = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            match phase {
+                Phase::Parse => {
+                    let reparsed = black_box(solver.parse(black_box(input))?);
+                    let elapsed = start.elapsed();
+                    drop(reparsed);
+                    return Ok(elapsed);
+                }
+                Phase::Part1 => black_box(solver.part1(black_box(&parsed))?),
+                Phase::Part2 => black_box(solver.part2(black_box(&parsed))?),
+            };
+            Ok(start.elapsed())
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    timings.sort_unstable();
+    Ok(timings[timings.len() / 2])
+}
+
+/// `git describe --always --dirty`, or `unknown` outside of a repository
+pub fn current_commit() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| String::from(commit.trim()))
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    /// Median runtime
+    pub runtime: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    pub commit: String,
+    pub timings: Vec<Timing>,
+}
+
+impl Run {
+    /// Time every phase of every solver with an input, returning the solvers that failed
+    pub fn measure<'a>(
+        commit: String,
+        solvers: impl IntoIterator<Item = &'a dyn Solver>,
+        inputs: &InputSource,
+        runs: usize,
+    ) -> (Self, Vec<anyhow::Error>) {
+        let mut timings = Vec::new();
+        let mut errors = Vec::new();
+        for solver in solvers {
+            let (year, day) = (solver.year(), solver.day());
+            let input = match inputs.load(year, day) {
+                Ok(input) => input,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            for phase in Phase::ALL {
+                match median_runtime(solver, phase, &input, runs) {
+                    Ok(runtime) => timings.push(Timing {
+                        year,
+                        day,
+                        phase,
+                        runtime,
+                    }),
+                    Err(e) => errors.push(e.context(format!("{} day {} {}", year, day, phase))),
+                }
+            }
+        }
+        (Self { commit, timings }, errors)
+    }
+
+    pub fn get(&self, year: u16, day: u8, phase: Phase) -> Option<Duration> {
+        self.timings
+            .iter()
+            .find(|timing| (timing.year, timing.day, timing.phase) == (year, day, phase))
+            .map(|timing| timing.runtime)
+    }
+
+    /// Add `timings`, replacing any of the same phase
+    fn merge(&mut self, timings: Vec<Timing>) {
+        for timing in timings {
+            match self.timings.iter_mut().find(|existing| {
+                (existing.year, existing.day, existing.phase)
+                    == (timing.year, timing.day, timing.phase)
+            }) {
+                Some(existing) => *existing = timing,
+                None => self.timings.push(timing),
+            }
+        }
+    }
+}
+
+/// Oldest first, with at most one run per commit.
+/// A run only has the phases that were timed, e.g `aoc bench --day 5` records day 5 alone.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("Corrupt benchmark history in {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n").with_context(|| format!("Couldn't write {}", path.display()))
+    }
+
+    /// The latest timing of a phase from a different commit, and the commit it was from.
+    /// Each phase is looked up on its own, so a partial run doesn't hide older timings.
+    pub fn baseline(
+        &self,
+        commit: &str,
+        year: u16,
+        day: u8,
+        phase: Phase,
+    ) -> Option<(&str, Duration)> {
+        self.runs
+            .iter()
+            .rev()
+            .filter(|run| run.commit != commit)
+            .find_map(|run| Some((run.commit.as_str(), run.get(year, day, phase)?)))
+    }
+
+    /// The latest timing of a phase from any commit
+    pub fn latest(&self, year: u16, day: u8, phase: Phase) -> Option<Duration> {
+        self.runs
+            .iter()
+            .rev()
+            .find_map(|run| run.get(year, day, phase))
+    }
+
+    /// Re-running a commit replaces the timings of the phases that were re-run,
+    /// and makes it the latest run
+    pub fn record(&mut self, run: Run) {
+        let run = match self
+            .runs
+            .iter()
+            .position(|existing| existing.commit == run.commit)
+        {
+            Some(index) => {
+                let mut existing = self.runs.remove(index);
+                existing.merge(run.timings);
+                existing
+            }
+            None => run,
+        };
+        self.runs.push(run);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub timing: Timing,
+    pub baseline: Option<Duration>,
+    /// The commit the baseline was timed at
+    pub against: Option<String>,
+}
+
+impl Comparison {
+    /// e.g `0.5` for a part that takes half as long again as the baseline
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_secs_f64();
+        match baseline > 0.0 {
+            true => Some(self.timing.runtime.as_secs_f64() / baseline - 1.0),
+            false => None,
+        }
+    }
+
+    pub fn regressed(&self, threshold: f64) -> bool {
+        matches!(self.change(), Some(change) if change > threshold)
+    }
+}
+
+/// Each timing against its [`History::baseline`]
+pub fn compare(run: &Run, history: &History) -> Vec<Comparison> {
+    run.timings
+        .iter()
+        .map(|timing| {
+            let baseline = history.baseline(&run.commit, timing.year, timing.day, timing.phase);
+            Comparison {
+                timing: timing.clone(),
+                baseline: baseline.map(|(_, runtime)| runtime),
+                against: baseline.map(|(commit, _)| String::from(commit)),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn takes_the_median() {
+    let solver = crate::registry::find(2021, 6).unwrap();
+    for phase in Phase::ALL {
+        let runtime = median_runtime(solver, phase, "3,4,3,1,2", 5).unwrap();
+        assert!(runtime < Duration::from_secs(1));
+        assert!(median_runtime(solver, phase, "not fish", 5).is_err());
+    }
+}
+
+#[test]
+fn detects_regressions() {
+    let run = |commit: &str, micros: u64| Run {
+        commit: String::from(commit),
+        timings: vec![Timing {
+            year: 2021,
+            day: 6,
+            phase: Phase::Part1,
+            runtime: Duration::from_micros(micros),
+        }],
+    };
+    let mut history = History::default();
+    history.record(run("abc1234", 100));
+    history.record(run("def5678", 200));
+    history.record(run("def5678", 105));
+    assert_eq!(history.runs.len(), 2);
+
+    // Compared against the previous commit, not an earlier run of this one
+    let latest = run("def5678", 125);
+    let comparison = &compare(&latest, &history)[0];
+    assert_eq!(comparison.baseline, Some(Duration::from_micros(100)));
+    assert_eq!(comparison.against.as_deref(), Some("abc1234"));
+    assert!(comparison.regressed(0.2));
+    assert!(!comparison.regressed(0.3));
+    assert!(!compare(&latest, &History::default())[0].regressed(0.0));
+}
+
+#[test]
+fn keeps_timings_from_partial_runs() {
+    let timing = |day: u8, micros: u64| Timing {
+        year: 2021,
+        day,
+        phase: Phase::Part1,
+        runtime: Duration::from_micros(micros),
+    };
+    let run = |commit: &str, timings: Vec<Timing>| Run {
+        commit: String::from(commit),
+        timings,
+    };
+    let mut history = History::default();
+    history.record(run("abc1234", vec![timing(5, 100), timing(6, 100)]));
+    // Just day 6, on the same commit then the next
+    history.record(run("abc1234", vec![timing(6, 90)]));
+    assert_eq!(
+        history.runs,
+        [run("abc1234", vec![timing(5, 100), timing(6, 90)])]
+    );
+    history.record(run("def5678", vec![timing(6, 80)]));
+
+    // Day 5 wasn't timed at def5678, so it's still compared against abc1234
+    let latest = run("0123abc", vec![timing(5, 150), timing(6, 85)]);
+    let comparisons = compare(&latest, &history);
+    assert_eq!(comparisons[0].against.as_deref(), Some("abc1234"));
+    assert!(comparisons[0].regressed(0.2));
+    assert_eq!(comparisons[1].against.as_deref(), Some("def5678"));
+    assert_eq!(
+        history.latest(2021, 5, Phase::Part1),
+        Some(Duration::from_micros(100))
+    );
+}
@@ -0,0 +1,321 @@
+//! Talking to adventofcode.com.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+
+use crate::input;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// As requested by the site's maintainer, so automated traffic can be traced back to a repository
+pub const USER_AGENT: &str = concat!(
+    "github.com/aatifsyed/advent-of-code-2021 v",
+    env!("CARGO_PKG_VERSION")
+);
+/// The in-browser `session` cookie, which may also be saved in `./.env`
+pub const SESSION_VAR: &str = "SESSION";
+
+#[derive(Debug, derive_more::Display)]
+pub enum ClientError {
+    #[display(fmt = "There is no puzzle for {} day {}", year, day)]
+    NoSuchPuzzle { year: u16, day: u8 },
+    #[display(
+        fmt = "{} day {} isn't unlocked yet, try again in {}s",
+        year,
+        day,
+        "unlocks_in.as_secs()"
+    )]
+    Locked {
+        year: u16,
+        day: u8,
+        unlocks_in: Duration,
+    },
+    #[display(
+        fmt = "Session cookie was rejected, log in again and update ${}",
+        SESSION_VAR
+    )]
+    SessionExpired,
+    #[display(fmt = "Unexpected response ({}): {}", status, body)]
+    Unexpected { status: u16, body: String },
+    #[display(fmt = "Couldn't reach the server: {}", _0)]
+    Transport(String),
+}
+
+impl std::error::Error for ClientError {}
+
+/// The last puzzle of each event
+pub fn last_day(year: u16) -> u8 {
+    match year {
+        ..=2024 => 25,
+        // The event was shortened from 2025 onwards
+        _ => 12,
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let shifted_month = (month + 9) % 12; // March is 0
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Puzzles unlock at midnight EST (05:00 UTC) on each day of December
+pub fn unlock_time(year: u16, day: u8) -> Option<SystemTime> {
+    if year < 2015 || !(1..=last_day(year)).contains(&day) {
+        return None;
+    }
+    let days = days_from_civil(year.into(), 12, day.into());
+    let seconds = days as u64 * 24 * 60 * 60 + 5 * 60 * 60;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+pub fn check_unlocked(year: u16, day: u8, now: SystemTime) -> Result<(), ClientError> {
+    let unlocks_at = unlock_time(year, day).ok_or(ClientError::NoSuchPuzzle { year, day })?;
+    match unlocks_at.duration_since(now) {
+        Ok(unlocks_in) if !unlocks_in.is_zero() => Err(ClientError::Locked {
+            year,
+            day,
+            unlocks_in,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Read `$SESSION`, or `SESSION=...` from `./.env`
+pub fn session_from_env() -> anyhow::Result<String> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        return Ok(session);
+    }
+    let dotenv = fs::read_to_string(".env").unwrap_or_default();
+    dotenv
+        .lines()
+        .filter_map(|line| line.trim().trim_start_matches("export ").split_once('='))
+        .find(|(key, _)| key.trim() == SESSION_VAR)
+        .map(|(_, value)| String::from(value.trim().trim_matches(|c| c == '"' || c == '\'')))
+        .with_context(|| {
+            format!(
+                "Set ${} to your in-browser session cookie. You can save it in ./.env",
+                SESSION_VAR
+            )
+        })
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: String::from(BASE_URL),
+            session: session.into(),
+        }
+    }
+
+    /// Point at a different server, e.g a local stub
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn from_env() -> anyhow::Result<Self> {
+        Ok(Self::new(session_from_env()?))
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url.trim_end_matches('/'), path)
+    }
+
+    pub(crate) fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub(crate) fn agent(&self) -> &ureq::Agent {
+        &self.agent
+    }
+
+    /// Fetch the puzzle input, without checking the release schedule or the cache
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .get(&self.url(&format!("/{}/day/{}/input", year, day)))
+            .set("Cookie", &self.cookie())
+            .call();
+        into_body(response)
+    }
+
+    /// Download the input to `<directory>/<year>/<day>.txt`, unless it's already there.
+    /// Refuses to ask for puzzles that haven't unlocked yet.
+    pub fn fetch_cached(&self, directory: &Path, year: u16, day: u8) -> anyhow::Result<PathBuf> {
+        let path = input::path_in(directory, year, day);
+        if path.exists() {
+            return Ok(path);
+        }
+        check_unlocked(year, day, SystemTime::now())?;
+        let input = self.input(year, day)?;
+
+        let parent = path.parent().expect("Path has year and day components");
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Couldn't create {}", parent.display()))?;
+        // Don't leave a half-written file behind to be mistaken for the cache
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, input)
+            .and_then(|_| fs::rename(&partial, &path))
+            .with_context(|| format!("Couldn't write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+pub(crate) fn into_body(
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| ClientError::Transport(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            match status {
+                // The site asks you to log in if the cookie is missing or stale
+                400 | 401 | 403 => Err(ClientError::SessionExpired),
+                _ if body.contains("log in") => Err(ClientError::SessionExpired),
+                _ => Err(ClientError::Unexpected { status, body }),
+            }
+        }
+        Err(ureq::Error::Transport(transport)) => {
+            Err(ClientError::Transport(transport.to_string()))
+        }
+    }
+}
+
+/// Serve each canned `(status, body)` to one connection in turn, returning the raw requests
+#[cfg(test)]
+pub(crate) fn stub_server(
+    responses: Vec<(u16, &'static str)>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            loop {
+                let n = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..n]);
+                let text = String::from_utf8_lossy(&request).to_lowercase();
+                if let Some(end_of_headers) = text.find("\r\n\r\n") {
+                    let content_length = text
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .map(|length| length.trim().parse::<usize>().unwrap())
+                        .unwrap_or(0);
+                    if request.len() >= end_of_headers + 4 + content_length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            requests.push(String::from_utf8(request).unwrap());
+        }
+        requests
+    });
+    (base_url, handle)
+}
+
+#[test]
+fn unlocks_at_midnight_est() {
+    // 2021-12-01T05:00:00Z
+    assert_eq!(
+        unlock_time(2021, 1),
+        Some(UNIX_EPOCH + Duration::from_secs(1638334800))
+    );
+    assert_eq!(unlock_time(2021, 26), None);
+    assert_eq!(unlock_time(2014, 1), None);
+
+    let just_before = UNIX_EPOCH + Duration::from_secs(1638334799);
+    assert!(matches!(
+        check_unlocked(2021, 1, just_before),
+        Err(ClientError::Locked { .. })
+    ));
+    assert!(check_unlocked(2021, 1, just_before + Duration::from_secs(1)).is_ok());
+}
+
+#[test]
+fn downloads_with_session_and_user_agent() {
+    let (base_url, server) = stub_server(vec![(200, "1\n2\n3\n")]);
+    let client = Client::new("deadbeef").with_base_url(base_url);
+    assert_eq!(client.input(2021, 1).unwrap(), "1\n2\n3\n");
+
+    let requests = server.join().unwrap();
+    let request = requests[0].to_lowercase();
+    assert!(request.starts_with("get /2021/day/1/input "), "{}", request);
+    assert!(request.contains("cookie: session=deadbeef"), "{}", request);
+    assert!(
+        request.contains(&format!("user-agent: {}", USER_AGENT.to_lowercase())),
+        "{}",
+        request
+    );
+}
+
+#[test]
+fn reports_expired_session() {
+    let (base_url, _server) = stub_server(vec![(
+        400,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+    )]);
+    let client = Client::new("stale").with_base_url(base_url);
+    assert!(matches!(
+        client.input(2021, 1),
+        Err(ClientError::SessionExpired)
+    ));
+}
+
+#[test]
+fn caches_downloads() {
+    let directory = env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+    let (base_url, server) = stub_server(vec![(200, "3,4,3,1,2\n")]);
+    let client = Client::new("deadbeef").with_base_url(base_url);
+
+    let path = client.fetch_cached(&directory, 2021, 6).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "3,4,3,1,2\n");
+    // The stub only answers once, so this must come from disk
+    assert_eq!(client.fetch_cached(&directory, 2021, 6).unwrap(), path);
+    assert_eq!(server.join().unwrap().len(), 1);
+
+    let locked = client.fetch_cached(&directory, 2099, 1).unwrap_err();
+    assert!(matches!(
+        locked.downcast_ref(),
+        Some(ClientError::Locked { .. })
+    ));
+    fs::remove_dir_all(directory).unwrap();
+}
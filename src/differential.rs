@@ -0,0 +1,140 @@
+//! Cross-checking different implementations of the same part on lots of [`generate`]d inputs,
+//! e.g a brute force solution against a clever one.
+//!
+//! Inputs start small and grow, and any disagreement is [`minimize`]d before it's reported.
+use std::fmt;
+
+use crate::{fuzz::minimize, generate};
+
+/// One way of solving a part, returning the answer as it would be printed
+#[derive(Debug, Clone, Copy)]
+pub struct Implementation {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&str) -> anyhow::Result<String>,
+}
+
+/// Every implementation of each part of a puzzle
+#[derive(Debug, Clone, Copy)]
+pub struct Implementations {
+    pub year: u16,
+    pub day: u8,
+    pub implementations: &'static [Implementation],
+}
+
+/// Days with more than one implementation of a part
+static IMPLEMENTATIONS: &[&Implementations] =
+    &[&crate::y2021::treachery_of_whales::IMPLEMENTATIONS];
+
+pub fn all() -> impl Iterator<Item = &'static Implementations> {
+    IMPLEMENTATIONS.iter().copied()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    /// Each implementation's name and answer, or error
+    pub answers: Vec<(&'static str, String)>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} day {} part {} disagrees on {:?}",
+            self.year, self.day, self.part, self.input
+        )?;
+        for (name, answer) in &self.answers {
+            writeln!(f, "  {}: {}", name, answer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Implementations {
+    fn answers(&self, part: u8, input: &str) -> Vec<(&'static str, String)> {
+        self.implementations
+            .iter()
+            .filter(|implementation| implementation.part == part)
+            .map(|implementation| {
+                let answer =
+                    (implementation.solve)(input).unwrap_or_else(|e| format!("error: {:#}", e));
+                (implementation.name, answer)
+            })
+            .collect()
+    }
+
+    fn disagree(&self, part: u8, input: &str) -> bool {
+        let answers = self.answers(part, input);
+        answers.iter().any(|(_, answer)| *answer != answers[0].1)
+    }
+
+    /// Run `cases` generated inputs through every implementation, returning the smallest disagreement
+    pub fn check(&self, cases: usize, seed: u64) -> Option<Disagreement> {
+        for case in 0..cases {
+            let size = 1 + case / 10;
+            let input =
+                generate::generate(self.year, self.day, size, seed.wrapping_add(case as u64))?;
+            for part in [1, 2] {
+                if self.disagree(part, &input) {
+                    let input = minimize(&input, |candidate| self.disagree(part, candidate));
+                    return Some(Disagreement {
+                        year: self.year,
+                        day: self.day,
+                        part,
+                        answers: self.answers(part, &input),
+                        input,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+#[test]
+fn implementations_agree() {
+    let disagreements = all()
+        .filter_map(|implementations| implementations.check(1000, 0))
+        .map(|disagreement| disagreement.to_string())
+        .collect::<Vec<_>>();
+    assert!(disagreements.is_empty(), "{}", disagreements.join("\n"));
+    // Any seed will do, even one that wraps around
+    assert!(all().all(|implementations| implementations.check(2, u64::MAX).is_none()));
+}
+
+#[test]
+fn reports_the_smallest_disagreement() {
+    fn positions(input: &str) -> anyhow::Result<Vec<usize>> {
+        Ok(input
+            .trim()
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<_, _>>()?)
+    }
+    // Both wrong whenever the crabs aren't all in the same place
+    static WRONG: Implementations = Implementations {
+        year: 2021,
+        day: 7,
+        implementations: &[
+            Implementation {
+                part: 1,
+                name: "furthest",
+                solve: |input| Ok(positions(input)?.iter().max().unwrap_or(&0).to_string()),
+            },
+            Implementation {
+                part: 1,
+                name: "nearest",
+                solve: |input| Ok(positions(input)?.iter().min().unwrap_or(&0).to_string()),
+            },
+        ],
+    };
+    let disagreement = WRONG.check(100, 0).unwrap();
+    assert_eq!(disagreement.part, 1);
+    // Two crabs, with single digit positions
+    assert_eq!(disagreement.input.len(), 3, "{:?}", disagreement.input);
+    assert_ne!(disagreement.answers[0].1, disagreement.answers[1].1);
+}
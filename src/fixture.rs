@@ -0,0 +1,101 @@
+//! The worked example from each puzzle's description, with its answers.
+//! These are checked in alongside the inputs as `<year>/<day>.example.toml`, e.g
+//! ```toml
+//! part1 = 7
+//! part2 = 5
+//! input = '''
+//! 199
+//! 200
+//! '''
+//! ```
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{ensure, Context};
+use serde::Deserialize;
+
+use crate::solution::Solver;
+
+/// Answers are written as TOML integers where possible, but some puzzles want text
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => number.fmt(f),
+            Answer::Text(text) => text.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    pub input: String,
+    /// Missing until the part has been solved
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Fixture {
+    pub fn path_in(directory: &Path, year: u16, day: u8) -> PathBuf {
+        directory
+            .join(year.to_string())
+            .join(format!("{}.example.toml", day))
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Couldn't read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Invalid fixture {}", path.display()))
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Run the solver on the example, comparing against the expected answer
+    pub fn check(&self, solver: &dyn Solver, part: u8) -> anyhow::Result<()> {
+        let expected = self
+            .answer(part)
+            .with_context(|| format!("No expected answer for part {}", part))?
+            .to_string();
+        let actual = solver.solve(part, &self.input)?;
+        ensure!(
+            actual == expected,
+            "{} day {} part {}: expected {}, got {}",
+            solver.year(),
+            solver.day(),
+            part,
+            expected,
+            actual
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn checks_answers() {
+    let fixture: Fixture = toml::from_str("part1 = 5934\ninput = '''\n3,4,3,1,2\n'''\n").unwrap();
+    assert_eq!(fixture.input, "3,4,3,1,2\n");
+    let solver = crate::registry::find(2021, 6).unwrap();
+    fixture.check(solver, 1).unwrap();
+    assert!(fixture.check(solver, 2).is_err());
+    assert!(Fixture {
+        part1: Some(Answer::Number(1)),
+        ..fixture
+    }
+    .check(solver, 1)
+    .is_err());
+}
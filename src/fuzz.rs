@@ -0,0 +1,300 @@
+//! A small mutation fuzzer for the parsers, which runs under `cargo test` on stable.
+//!
+//! Seeds are the real inputs, worked examples and some [`crate::generate`]d inputs,
+//! which are mangled the way corrupted files tend to be: changed characters, dropped or repeated lines, huge numbers.
+//! Each case runs on its own thread, so a hang is reported instead of stalling the test.
+use std::{
+    cell::Cell,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{mpsc, Once},
+    thread,
+    time::Duration,
+};
+
+use rand::{seq::IndexedRandom, Rng};
+
+use crate::{fixture::Fixture, generate, input::InputSource, solution::Solver};
+
+/// Characters that are likely to confuse a parser
+const INTERESTING: &[&str] = &[
+    "0",
+    "1",
+    "9",
+    "-",
+    ",",
+    " ",
+    "\n",
+    "\n\n",
+    "\r\n",
+    "\t",
+    "|",
+    "->",
+    "(",
+    ">",
+    "a",
+    "g",
+    "z",
+    "🦑",
+    "65535",
+    "65536",
+    "4294967296",
+    "18446744073709551616",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Panic(String),
+    Timeout,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panic(message) => write!(f, "panicked with {:?}", message),
+            Failure::Timeout => write!(f, "timed out"),
+        }
+    }
+}
+
+thread_local! {
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Panics in [`guard`] are reported as [`Failure`]s, so printing them as well is just noise.
+/// The hook is only installed once and passes other threads' panics on,
+/// so tests calling [`guard`] in parallel don't fight over it.
+fn silence_guarded_panics() {
+    static SILENCE: Once = Once::new();
+    SILENCE.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !GUARDED.with(Cell::get) {
+                hook(info)
+            }
+        }));
+    });
+}
+
+/// Run `f` on another thread, catching panics and giving up after `timeout`.
+/// A timed out thread is left running, there's no way to stop it.
+pub fn guard(timeout: Duration, f: impl FnOnce() + Send + 'static) -> Result<(), Failure> {
+    silence_guarded_panics();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        GUARDED.with(|guarded| guarded.set(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+            payload
+                .downcast_ref::<&str>()
+                .map(|message| String::from(*message))
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default()
+        });
+        let _ = sender.send(result);
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result.map_err(Failure::Panic),
+        Err(_) => Err(Failure::Timeout),
+    }
+}
+
+/// Parse, and if that succeeds, solve both parts
+pub fn check_solver(
+    solver: &'static dyn Solver,
+    input: &str,
+    timeout: Duration,
+) -> Result<(), Failure> {
+    let input = String::from(input);
+    guard(timeout, move || {
+        if let Ok(parsed) = solver.parse(&input) {
+            let _ = solver.part1(&parsed);
+            let _ = solver.part2(&parsed);
+        }
+    })
+}
+
+pub fn check_parser(
+    solver: &'static dyn Solver,
+    input: &str,
+    timeout: Duration,
+) -> Result<(), Failure> {
+    let input = String::from(input);
+    guard(timeout, move || {
+        let _ = solver.parse(&input);
+    })
+}
+
+fn char_boundary(s: &str, rng: &mut impl Rng) -> usize {
+    let mut index = rng.random_range(0..=s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Apply a few random changes to `input`, sometimes splicing in part of `other`
+pub fn mutate(input: &str, other: &str, rng: &mut impl Rng) -> String {
+    let mut mutated = String::from(input);
+    for _ in 0..rng.random_range(1..=4) {
+        let start = char_boundary(&mutated, rng);
+        let end = start + char_boundary(&mutated[start..], rng);
+        match rng.random_range(0..7) {
+            // Replace a character
+            0 => {
+                let len = mutated[start..].chars().next().map_or(0, char::len_utf8);
+                let replacement = INTERESTING.choose(rng).copied().unwrap_or_default();
+                mutated.replace_range(start..start + len, replacement);
+            }
+            1 => mutated.insert_str(start, INTERESTING.choose(rng).copied().unwrap_or_default()),
+            2 => mutated.replace_range(start..end, ""),
+            3 => {
+                let repeated = String::from(&mutated[start..end]);
+                mutated.insert_str(end, &repeated);
+            }
+            4 => mutated.truncate(start),
+            // A line from the other input
+            5 => {
+                let lines = other.lines().collect::<Vec<_>>();
+                if let Some(line) = lines.choose(rng) {
+                    mutated.insert_str(start, &format!("{}\n", line));
+                }
+            }
+            // Swap two lines
+            _ => {
+                let mut lines = mutated.lines().map(String::from).collect::<Vec<_>>();
+                if lines.len() > 1 {
+                    let (a, b) = (
+                        rng.random_range(0..lines.len()),
+                        rng.random_range(0..lines.len()),
+                    );
+                    lines.swap(a, b);
+                    mutated = lines.join("\n") + "\n";
+                }
+            }
+        }
+    }
+    mutated
+}
+
+/// Make a failing input smaller, first by lines then by characters, while it fails the same way
+pub fn minimize(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut input = String::from(input);
+    let mut lines = input.lines().map(String::from).collect::<Vec<_>>();
+    let mut index = 0;
+    while index < lines.len() {
+        let mut candidate = lines.clone();
+        candidate.remove(index);
+        match fails(&(candidate.join("\n") + "\n")) {
+            true => lines = candidate,
+            false => index += 1,
+        }
+    }
+    if fails(&(lines.join("\n") + "\n")) {
+        input = lines.join("\n") + "\n";
+    }
+    let mut index = 0;
+    while index < input.len() {
+        let len = input[index..].chars().next().map_or(1, char::len_utf8);
+        let mut candidate = input.clone();
+        candidate.replace_range(index..index + len, "");
+        match fails(&candidate) {
+            true => input = candidate,
+            false => index += len,
+        }
+    }
+    input
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub failure: Failure,
+    /// As small as [`minimize`] could make it
+    pub input: String,
+}
+
+/// The real input, the worked example and a few generated inputs, whichever are available
+pub fn seeds(solver: &dyn Solver, inputs: &InputSource, fixtures: &Path) -> Vec<String> {
+    let (year, day) = (solver.year(), solver.day());
+    inputs
+        .load(year, day)
+        .ok()
+        .into_iter()
+        .chain(
+            Fixture::load(&Fixture::path_in(fixtures, year, day))
+                .ok()
+                .map(|fixture| fixture.input),
+        )
+        .chain(
+            [1, 10, 100]
+                .map(|size| generate::generate(year, day, size, 0))
+                .into_iter()
+                .flatten(),
+        )
+        .collect()
+}
+
+/// Mutate `seeds` for `iterations` rounds, stopping at the first failing input
+pub fn fuzz(
+    solver: &'static dyn Solver,
+    seeds: &[String],
+    iterations: usize,
+    timeout: Duration,
+    rng: &mut impl Rng,
+) -> Option<Finding> {
+    let empty = [String::new()];
+    let seeds = match seeds.is_empty() {
+        true => &empty[..],
+        false => seeds,
+    };
+    for _ in 0..iterations {
+        let seed = seeds.choose(rng)?;
+        let other = seeds.choose(rng)?;
+        let input = mutate(seed, other, rng);
+        if let Err(failure) = check_parser(solver, &input, timeout) {
+            // Shrinking a hang would take forever
+            let input = match failure {
+                Failure::Panic(_) => minimize(&input, |candidate| {
+                    matches!(
+                        check_parser(solver, candidate, timeout),
+                        Err(Failure::Panic(_))
+                    )
+                }),
+                Failure::Timeout => input,
+            };
+            return Some(Finding { failure, input });
+        }
+    }
+    None
+}
+
+#[test]
+fn mutates_deterministically() {
+    use rand::SeedableRng;
+    let mutations = |seed| {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+        (0..20)
+            .map(|_| mutate("forward 5\ndown 5\n", "up 3\n", &mut rng))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(mutations(1), mutations(1));
+    assert_ne!(mutations(1), mutations(2));
+}
+
+#[test]
+fn catches_panics_and_hangs() {
+    let timeout = Duration::from_millis(100);
+    assert_eq!(guard(timeout, || ()), Ok(()));
+    assert_eq!(
+        guard(timeout, || panic!("oops")),
+        Err(Failure::Panic(String::from("oops")))
+    );
+    assert_eq!(
+        guard(timeout, || thread::sleep(Duration::from_secs(1))),
+        Err(Failure::Timeout)
+    );
+    assert_eq!(
+        minimize("1\n2\n3\n", |candidate| candidate.contains('2')),
+        "2"
+    );
+}
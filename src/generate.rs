@@ -0,0 +1,214 @@
+//! Random puzzle inputs of any size, for stress testing and seeing how solutions scale.
+//! Everything is valid by the puzzle's rules, not just the parser's, and reproducible from a seed.
+//!
+//! What `size` counts depends on the day, e.g depths for `sonar_sweep`, boards for `giant_squid`.
+use itertools::Itertools;
+use rand::{
+    seq::{IndexedRandom, SliceRandom},
+    Rng, SeedableRng,
+};
+use rand_chacha::ChaCha8Rng;
+
+/// An input for the puzzle, or [`None`] if there's no generator for it
+pub fn generate(year: u16, day: u8, size: usize, seed: u64) -> Option<String> {
+    let rng = &mut ChaCha8Rng::seed_from_u64(seed);
+    let input = match (year, day) {
+        (2021, 1) => sonar_sweep(size, rng),
+        (2021, 2) => dive(size, rng),
+        (2021, 3) => binary_diagnostic(size, rng),
+        (2021, 4) => giant_squid(size, rng),
+        (2021, 5) => hydrothermal_venture(size, rng),
+        (2021, 6) => lanternfish(size, rng),
+        (2021, 7) => treachery_of_whales(size, rng),
+        (2021, 8) => seven_segment_search(size, rng),
+        (2021, 9) => smoke_basin(size, rng),
+        (2021, 10) => syntax_scoring(size, rng),
+        _ => return None,
+    };
+    Some(input)
+}
+
+fn lines(size: usize, mut line: impl FnMut() -> String) -> String {
+    (0..size).map(|_| line() + "\n").collect()
+}
+
+/// Depths which mostly increase, like the sea floor
+fn sonar_sweep(size: usize, rng: &mut impl Rng) -> String {
+    let mut depth = rng.random_range(100..200u32);
+    lines(size, || {
+        depth = depth.saturating_add_signed(rng.random_range(-20..=30));
+        depth.to_string()
+    })
+}
+
+/// Never goes above the surface
+fn dive(size: usize, rng: &mut impl Rng) -> String {
+    let mut depth = 0;
+    lines(size, || {
+        let distance = rng.random_range(1..10);
+        match rng.random_range(0..3) {
+            0 => format!("forward {}", distance),
+            1 => {
+                depth += distance;
+                format!("down {}", distance)
+            }
+            _ if depth >= distance => {
+                depth -= distance;
+                format!("up {}", distance)
+            }
+            _ => format!("down {}", distance),
+        }
+    })
+}
+
+fn binary_diagnostic(size: usize, rng: &mut impl Rng) -> String {
+    lines(size, || format!("{:012b}", rng.random_range(0..1 << 12)))
+}
+
+/// Every number is drawn, so every board wins eventually
+fn giant_squid(size: usize, rng: &mut impl Rng) -> String {
+    let numbers = (0..100).collect_vec();
+    let mut draws = numbers.clone();
+    draws.shuffle(rng);
+    let mut boards = (0..size).map(|_| {
+        numbers
+            .choose_multiple(rng, 25)
+            .collect_vec()
+            .chunks(5)
+            .map(|row| row.iter().map(|number| format!("{:>2}", number)).join(" "))
+            .join("\n")
+    });
+    format!("{}\n\n{}\n", draws.iter().join(","), boards.join("\n\n"))
+}
+
+/// Horizontal, vertical, or at exactly 45 degrees
+fn hydrothermal_venture(size: usize, rng: &mut impl Rng) -> String {
+    const EXTENT: i32 = 1000;
+    lines(size, || {
+        let (x1, y1) = (rng.random_range(0..EXTENT), rng.random_range(0..EXTENT));
+        let (dx, dy) = *[(1, 0), (0, 1), (1, 1), (1, -1)].choose(rng).unwrap();
+        // Stay on the floor in both directions
+        let limit = |start: i32, step: i32| match step {
+            1 => EXTENT - 1 - start,
+            -1 => start,
+            _ => i32::MAX,
+        };
+        let length = rng.random_range(0..=limit(x1, dx).min(limit(y1, dy)).min(100));
+        format!("{},{} -> {},{}", x1, y1, x1 + dx * length, y1 + dy * length)
+    })
+}
+
+fn lanternfish(size: usize, rng: &mut impl Rng) -> String {
+    (0..size).map(|_| rng.random_range(1..=5)).join(",") + "\n"
+}
+
+fn treachery_of_whales(size: usize, rng: &mut impl Rng) -> String {
+    (0..size).map(|_| rng.random_range(0..2000)).join(",") + "\n"
+}
+
+/// The segments lit for each digit, with the display wired correctly
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Each display has its own wiring, and lists its patterns and segments in any order
+fn seven_segment_search(size: usize, rng: &mut impl Rng) -> String {
+    lines(size, || {
+        let mut wiring = ('a'..='g').collect_vec();
+        wiring.shuffle(rng);
+        let mut digits = (0..10).collect_vec();
+        digits.shuffle(rng);
+        digits.extend((0..4).map(|_| rng.random_range(0..10)));
+        let mut patterns = digits
+            .into_iter()
+            .map(|digit| {
+                let mut segments = DIGITS[digit]
+                    .chars()
+                    .map(|segment| wiring[segment as usize - 'a' as usize])
+                    .collect_vec();
+                segments.shuffle(rng);
+                segments.into_iter().collect::<String>()
+            })
+            .collect_vec();
+        let outputs = patterns.split_off(10);
+        format!("{} | {}", patterns.join(" "), outputs.join(" "))
+    })
+}
+
+/// `size` rows of 100, where walls of 9 split the map into basins
+fn smoke_basin(size: usize, rng: &mut impl Rng) -> String {
+    const WIDTH: usize = 100;
+    let walls = (0..WIDTH).map(|_| rng.random_bool(0.2)).collect_vec();
+    lines(size, || {
+        let is_wall = rng.random_bool(0.2);
+        walls
+            .iter()
+            .map(|column| match is_wall || *column || rng.random_bool(0.05) {
+                true => '9',
+                false => char::from(b'0' + rng.random_range(0..9)),
+            })
+            .collect()
+    })
+}
+
+/// A mix of corrupted and incomplete lines, none of them complete
+fn syntax_scoring(size: usize, rng: &mut impl Rng) -> String {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    lines(size, || {
+        let mut line = String::new();
+        let mut open = Vec::new();
+        for _ in 0..rng.random_range(20..100) {
+            // Not too deep, or the completion score overflows
+            match open.len() < 20 && (open.is_empty() || rng.random_bool(0.6)) {
+                true => {
+                    let (opening, closing) = *PAIRS.choose(rng).unwrap();
+                    line.push(opening);
+                    open.push(closing);
+                }
+                false => line.extend(open.pop()),
+            }
+        }
+        match open.last() {
+            // Corrupted
+            Some(expected) if rng.random_bool(0.5) => line.extend(
+                PAIRS
+                    .iter()
+                    .map(|(_, closing)| *closing)
+                    .find(|closing| closing != expected),
+            ),
+            Some(_) => (),
+            // Would be complete
+            None => line.push(PAIRS.choose(rng).unwrap().0),
+        }
+        line
+    })
+}
+
+#[test]
+fn reproducible() {
+    assert_eq!(generate(2021, 4, 3, 1), generate(2021, 4, 3, 1));
+    assert_ne!(generate(2021, 4, 3, 1), generate(2021, 4, 3, 2));
+    assert_eq!(generate(2021, 26, 3, 1), None);
+}
+
+#[test]
+fn solvable() {
+    for solver in crate::registry::all() {
+        for seed in 0..5 {
+            let input = generate(solver.year(), solver.day(), 50, seed).unwrap();
+            for part in [1, 2] {
+                if let Err(e) = solver.solve(part, &input) {
+                    panic!(
+                        "{} day {} part {} with seed {}: {:#}\n{}",
+                        solver.year(),
+                        solver.day(),
+                        part,
+                        seed,
+                        e,
+                        input
+                    );
+                }
+            }
+        }
+    }
+}
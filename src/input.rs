@@ -0,0 +1,168 @@
+//! Finding puzzle inputs at runtime, rather than baking them into the library.
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+
+use crate::generate;
+
+/// Overrides the default input directory
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "src/inputs";
+
+// (year, day, input), populated by build.rs with the `embed-inputs` feature
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// Inputs laid out as `<directory>/<year>/<day>.txt`, falling back to [`InputSource::Embedded`]
+    Directory(PathBuf),
+    /// A single file, whatever the puzzle
+    Path(PathBuf),
+    Stdin,
+    /// Inputs compiled in with the `embed-inputs` feature
+    Embedded,
+    /// Random inputs from [`crate::generate`]
+    Generated {
+        size: usize,
+        seed: u64,
+    },
+}
+
+/// `$AOC_INPUT_DIR`, or `src/inputs`
+pub fn default_directory() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Directory(default_directory())
+    }
+}
+
+impl InputSource {
+    /// Interpret a command line argument, where `-` means stdin
+    pub fn from_arg(arg: impl Into<PathBuf>) -> Self {
+        let path = arg.into();
+        match path.as_os_str() == "-" {
+            true => Self::Stdin,
+            false => Self::Path(path),
+        }
+    }
+
+    pub fn load(&self, year: u16, day: u8) -> anyhow::Result<String> {
+        match self {
+            InputSource::Directory(directory) => {
+                let path = path_in(directory, year, day);
+                match fs::read_to_string(&path) {
+                    Ok(input) => Ok(input),
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => match embedded(year, day) {
+                        Some(input) => Ok(String::from(input)),
+                        None => bail!(
+                            "No input for {} day {}: expected a file at {}. \
+                            Download it with `aoc download`, or set ${} to a directory laid out as <year>/<day>.txt",
+                            year,
+                            day,
+                            path.display(),
+                            INPUT_DIR_VAR,
+                        ),
+                    },
+                    Err(e) => {
+                        Err(e).with_context(|| format!("Couldn't read {}", path.display()))
+                    }
+                }
+            }
+            InputSource::Path(path) => fs::read_to_string(path)
+                .with_context(|| format!("Couldn't read {}", path.display())),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Couldn't read stdin")?;
+                Ok(input)
+            }
+            InputSource::Embedded => match embedded(year, day) {
+                Some(input) => Ok(String::from(input)),
+                None if EMBEDDED.is_empty() => {
+                    bail!("No inputs are embedded, rebuild with --features embed-inputs")
+                }
+                None => bail!("No embedded input for {} day {}", year, day),
+            },
+            InputSource::Generated { size, seed } => generate::generate(year, day, *size, *seed)
+                .with_context(|| format!("Can't generate inputs for {} day {}", year, day)),
+        }
+    }
+
+    /// Like [`InputSource::load`], but files and stdin are read as they're used rather than all at once
+    pub fn reader(&self, year: u16, day: u8) -> anyhow::Result<Box<dyn BufRead>> {
+        let open = |path: &Path| -> anyhow::Result<Box<dyn BufRead>> {
+            let file =
+                File::open(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+            Ok(Box::new(BufReader::new(file)))
+        };
+        match self {
+            InputSource::Directory(directory) if path_in(directory, year, day).exists() => {
+                open(&path_in(directory, year, day))
+            }
+            InputSource::Path(path) => open(path),
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            // Already in memory, or an error
+            _ => Ok(Box::new(Cursor::new(self.load(year, day)?))),
+        }
+    }
+}
+
+pub fn path_in(directory: &Path, year: u16, day: u8) -> PathBuf {
+    directory
+        .join(year.to_string())
+        .join(format!("{}.txt", day))
+}
+
+fn embedded(year: u16, day: u8) -> Option<&'static str> {
+    EMBEDDED
+        .iter()
+        .find(|(y, d, _)| *y == year && *d == day)
+        .map(|(_, _, input)| *input)
+}
+
+/// For tests and benchmarks, which can't do anything useful without the input
+pub(crate) fn puzzle_input(year: u16, day: u8) -> String {
+    InputSource::default()
+        .load(year, day)
+        .unwrap_or_else(|e| panic!("{:#}", e))
+}
+
+#[test]
+fn missing_input_explains_layout() {
+    let source = InputSource::Directory(PathBuf::from("does/not/exist"));
+    let message = source.load(2021, 26).unwrap_err().to_string();
+    assert!(
+        message.contains("does/not/exist/2021/26.txt"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn reads_from_directory() {
+    let source = InputSource::Directory(PathBuf::from(DEFAULT_INPUT_DIR));
+    assert!(source.load(2021, 6).unwrap().starts_with(char::is_numeric));
+}
+
+#[test]
+fn reads_the_same_as_loading() {
+    let source = InputSource::Directory(PathBuf::from(DEFAULT_INPUT_DIR));
+    let mut read = String::new();
+    source
+        .reader(2021, 6)
+        .unwrap()
+        .read_to_string(&mut read)
+        .unwrap();
+    assert_eq!(read, source.load(2021, 6).unwrap());
+}
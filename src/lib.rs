@@ -1,24 +1,21 @@
 #![allow(dead_code)]
-#![feature(
-    bool_to_option,
-    hash_drain_filter,
-    if_let_guard,
-    int_abs_diff,
-    iter_zip,
-    option_result_contains,
-    test,
-    try_blocks
-)]
+#![cfg_attr(all(test, feature = "nightly"), feature(test))]
 
-mod binary_diagnostic;
-mod dive;
-mod giant_squid;
-mod hydrothermal_venture;
-mod lanternfish;
-mod seven_segment_search;
-mod smoke_basin;
-mod sonar_sweep;
-mod treachery_of_whales;
-mod utils;
-mod syntax_scoring;
-mod dumbo_octopus;
+pub mod animate;
+pub mod bench;
+pub mod client;
+pub mod differential;
+pub mod fixture;
+pub mod fuzz;
+pub mod generate;
+pub mod input;
+pub mod parse;
+pub mod readme;
+pub mod registry;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod submit;
+pub mod utils;
+pub mod visualize;
+pub mod y2021;
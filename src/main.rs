@@ -0,0 +1,889 @@
+use std::{
+    fs, io,
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant, SystemTime},
+};
+
+use advent_of_code::{
+    animate, bench,
+    client::{Client, ClientError},
+    input::{self, InputSource},
+    parse::ParseError,
+    readme, registry, scaffold,
+    solution::Solver,
+    submit::{self, Rejection, Verdict},
+    visualize,
+};
+use anyhow::Context;
+use clap::Parser;
+use itertools::Itertools;
+use rayon::prelude::*;
+
+/// Loosely following sysexits.h
+mod exit {
+    pub const SOLVER_FAILED: u8 = 1;
+    pub const REGRESSED: u8 = 3;
+    pub const UNKNOWN_PUZZLE: u8 = 64;
+    pub const PARSE_FAILED: u8 = 65;
+    pub const NO_INPUT: u8 = 66;
+    pub const UNAVAILABLE: u8 = 69;
+    pub const TRY_LATER: u8 = 75;
+    pub const NO_PERMISSION: u8 = 77;
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions")]
+enum Command {
+    /// Print the answer for a puzzle
+    Run(Run),
+    /// Download a puzzle input, if it isn't already in the input directory
+    Download(Download),
+    /// Create a module for a new puzzle, and register it
+    New(New),
+    /// Submit an answer, unless it's already known to be wrong
+    Submit(Submit),
+    /// Regenerate the table of puzzles in README.md. Run with --release for meaningful timings
+    Readme(Readme),
+    /// Time every part, comparing against the last recorded commit. Run with --release
+    Bench(Bench),
+    /// Draw a puzzle's state, to the terminal or an image file
+    Draw(Draw),
+    /// Record a simulation step by step, replaying it in the terminal or saving it
+    Animate(Animate),
+}
+
+#[derive(Debug, clap::Args)]
+struct Run {
+    /// Defaults to 2021, or every year with --all
+    #[arg(long)]
+    year: Option<u16>,
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+    /// Only run this part, printing just the answer
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Path to the puzzle input, or `-` for stdin
+    #[arg(long, conflicts_with_all = ["input_dir", "embedded"])]
+    input: Option<PathBuf>,
+    #[command(flatten)]
+    inputs: Inputs,
+    #[command(flatten)]
+    generated: Generated,
+    /// Run every puzzle, printing a table
+    #[arg(long, conflicts_with_all = ["day", "part", "input"])]
+    all: bool,
+    /// How many puzzles to run at once with --all, defaulting to one per CPU
+    #[arg(long, conflicts_with = "day")]
+    jobs: Option<NonZeroUsize>,
+    /// Solve the part as the input is read, for inputs too big to fit in memory.
+    /// Only some days support this
+    #[arg(long, requires = "part")]
+    stream: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct Download {
+    #[arg(long, default_value_t = 2021)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// Defaults to `$AOC_INPUT_DIR`, or `src/inputs`
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+struct New {
+    #[arg(long, default_value_t = 2021)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// The puzzle title in snake_case, e.g `treachery_of_whales`
+    #[arg(long)]
+    name: String,
+}
+
+#[derive(Debug, clap::Args)]
+struct Submit {
+    #[arg(long, default_value_t = 2021)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Defaults to running the solver on the puzzle input
+    #[arg(long)]
+    answer: Option<String>,
+    #[command(flatten)]
+    inputs: Inputs,
+}
+
+#[derive(Debug, clap::Args)]
+struct Readme {
+    /// Parts `aoc bench` never recorded are timed as the median of this many runs
+    #[arg(long, default_value_t = 11)]
+    runs: usize,
+    /// Where `aoc bench` keeps its timings
+    #[arg(long, default_value = "bench-history.json")]
+    bench_history: PathBuf,
+    #[command(flatten)]
+    inputs: Inputs,
+}
+
+#[derive(Debug, clap::Args)]
+struct Bench {
+    /// Only time this year
+    #[arg(long)]
+    year: Option<u16>,
+    /// Only time this day
+    #[arg(long, requires = "year")]
+    day: Option<u8>,
+    /// Timings are the median of this many runs
+    #[arg(long, default_value_t = 11)]
+    runs: usize,
+    /// Fail if a part is this many percent slower than the baseline
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+    /// Where timings are kept, keyed by git commit
+    #[arg(long, default_value = "bench-history.json")]
+    history: PathBuf,
+    /// Compare without recording this run
+    #[arg(long)]
+    no_save: bool,
+    #[command(flatten)]
+    inputs: Inputs,
+    // Timings on generated inputs aren't compared or recorded
+    #[command(flatten)]
+    generated: Generated,
+}
+
+#[derive(Debug, clap::Args)]
+struct Draw {
+    #[arg(long, default_value_t = 2021)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// Which picture to draw, defaulting to the day's first
+    #[arg(long)]
+    picture: Option<String>,
+    /// Write a `.png` or `.ppm` instead of printing to the terminal
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Draw each cell this many pixels across
+    #[arg(long, default_value_t = NonZeroUsize::MIN)]
+    scale: NonZeroUsize,
+    /// Path to the puzzle input, or `-` for stdin
+    #[arg(long, conflicts_with_all = ["input_dir", "embedded"])]
+    input: Option<PathBuf>,
+    #[command(flatten)]
+    inputs: Inputs,
+    #[command(flatten)]
+    generated: Generated,
+}
+
+#[derive(Debug, clap::Args)]
+struct Animate {
+    #[arg(long, default_value_t = 2021)]
+    year: u16,
+    #[arg(long)]
+    day: u8,
+    /// Which recording to make, defaulting to the day's first
+    #[arg(long)]
+    recording: Option<String>,
+    /// Write a `.gif`, or PNG frames into a directory, instead of replaying in the terminal
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Frames per second
+    #[arg(long, default_value = "10")]
+    fps: NonZeroU32,
+    /// Draw each cell this many pixels across
+    #[arg(long, default_value_t = NonZeroUsize::MIN)]
+    scale: NonZeroUsize,
+    /// Path to the puzzle input, or `-` for stdin
+    #[arg(long, conflicts_with_all = ["input_dir", "embedded"])]
+    input: Option<PathBuf>,
+    #[command(flatten)]
+    inputs: Inputs,
+    #[command(flatten)]
+    generated: Generated,
+}
+
+#[derive(Debug, clap::Args)]
+struct Inputs {
+    /// Directory of `<year>/<day>.txt` inputs.
+    /// Defaults to `$AOC_INPUT_DIR`, or `src/inputs`
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+    /// Only use inputs compiled in with the `embed-inputs` feature
+    #[arg(long, conflicts_with = "input_dir")]
+    embedded: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct Generated {
+    /// Use a random input of this size instead, e.g the number of lines
+    #[arg(long, conflicts_with_all = ["input_dir", "embedded"])]
+    generate: Option<usize>,
+    /// Seed for --generate
+    #[arg(long, requires = "generate", default_value_t = 0)]
+    seed: u64,
+}
+
+impl Generated {
+    fn or(&self, source: InputSource) -> InputSource {
+        match self.generate {
+            Some(size) => InputSource::Generated {
+                size,
+                seed: self.seed,
+            },
+            None => source,
+        }
+    }
+}
+
+impl Inputs {
+    fn source(&self) -> InputSource {
+        match (&self.input_dir, self.embedded) {
+            (_, true) => InputSource::Embedded,
+            (Some(directory), false) => InputSource::Directory(directory.clone()),
+            (None, false) => InputSource::default(),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match Command::parse() {
+        Command::Run(run) if run.all => {
+            run_all(run.year, run.generated.or(run.inputs.source()), run.jobs)
+        }
+        Command::Run(run) => run_one(run),
+        Command::Download(download) => download_input(download),
+        Command::New(new) => new_entry(new),
+        Command::Submit(submit) => submit_answer(submit),
+        Command::Readme(readme) => update_readme(readme),
+        Command::Bench(bench) => run_bench(bench),
+        Command::Draw(draw) => draw_picture(draw),
+        Command::Animate(animate) => record_animation(animate),
+    }
+}
+
+/// Something a day can show, as `(year, day, name, show)`
+type Shown<T> = (u16, u8, &'static str, fn(&str) -> anyhow::Result<T>);
+
+/// What `aoc draw` and `aoc animate` share: pick one of the day's pictures or recordings by `name`,
+/// or the first if there's no name, and run it on the day's input.
+/// Returns the name of the one picked, or the exit code once the error's been reported
+fn show<T>(
+    (verb, noun): (&str, &str),
+    all: impl Iterator<Item = Shown<T>>,
+    (year, day): (u16, u8),
+    name: Option<&str>,
+    source: &InputSource,
+) -> Result<(&'static str, T), ExitCode> {
+    let all = all.collect_vec();
+    let of_day = all
+        .iter()
+        .filter(|shown| (shown.0, shown.1) == (year, day))
+        .collect_vec();
+    if of_day.is_empty() {
+        let days = all
+            .iter()
+            .map(|shown| format!("{} day {}", shown.0, shown.1))
+            .dedup()
+            .join(", ");
+        eprintln!(
+            "error: nothing to {} for {} day {}, try {}",
+            verb, year, day, days
+        );
+        return Err(ExitCode::from(exit::UNKNOWN_PUZZLE));
+    }
+    let found = match name {
+        Some(name) => of_day.iter().find(|shown| shown.2 == name),
+        None => of_day.first(),
+    };
+    let Some(&&(_, _, found, run)) = found else {
+        eprintln!(
+            "error: no {} called {:?} for {} day {}, try {}",
+            noun,
+            name.unwrap_or_default(),
+            year,
+            day,
+            of_day.iter().map(|shown| shown.2).format(", ")
+        );
+        return Err(ExitCode::from(exit::UNKNOWN_PUZZLE));
+    };
+    let input = source.load(year, day).map_err(|e| {
+        eprintln!("error: {:#}", e);
+        ExitCode::from(exit::NO_INPUT)
+    })?;
+    match run(&input) {
+        Ok(shown) => Ok((found, shown)),
+        Err(e) => match e.downcast_ref::<ParseError>() {
+            Some(parse_error) => {
+                eprintln!("{}", parse_error.render());
+                Err(ExitCode::from(exit::PARSE_FAILED))
+            }
+            None => {
+                eprintln!("error: couldn't {} {}: {:#}", verb, found, e);
+                Err(ExitCode::from(exit::SOLVER_FAILED))
+            }
+        },
+    }
+}
+
+fn record_animation(args: Animate) -> ExitCode {
+    let delay = Duration::from_secs(1) / args.fps.get();
+    let source = match args.input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => args.generated.or(args.inputs.source()),
+    };
+    let recordings = animate::all().map(|recording| {
+        (
+            recording.year,
+            recording.day,
+            recording.name,
+            recording.record,
+        )
+    });
+    let (name, animation) = match show(
+        ("record", "recording"),
+        recordings,
+        (args.year, args.day),
+        args.recording.as_deref(),
+        &source,
+    ) {
+        Ok(shown) => shown,
+        Err(code) => return code,
+    };
+    let animation = match animation.scale(args.scale.get()) {
+        Ok(animation) => animation,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = match &args.output {
+        Some(path) => animation.save(path, delay).map(|()| {
+            eprintln!(
+                "Recorded {} frames of {} to {}",
+                animation.frames().len(),
+                name,
+                path.display()
+            )
+        }),
+        None => animation.replay(io::stdout().lock(), delay),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn draw_picture(args: Draw) -> ExitCode {
+    let source = match args.input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => args.generated.or(args.inputs.source()),
+    };
+    let pictures =
+        visualize::all().map(|picture| (picture.year, picture.day, picture.name, picture.draw));
+    let (name, image) = match show(
+        ("draw", "picture"),
+        pictures,
+        (args.year, args.day),
+        args.picture.as_deref(),
+        &source,
+    ) {
+        Ok(shown) => shown,
+        Err(code) => return code,
+    };
+    let image = match image.scale(args.scale.get()) {
+        Ok(image) => image,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match args.output {
+        Some(path) => match image.save(&path) {
+            Ok(()) => {
+                eprintln!("Drew {} to {}", name, path.display());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("error: {:#}", e);
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            print!("{}", image);
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+fn run_bench(args: Bench) -> ExitCode {
+    let generated = args.generated.generate.is_some();
+    let mut history = match generated {
+        true => bench::History::default(),
+        false => match bench::History::load(&args.history) {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("error: {:#}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let solvers = registry::all().filter(|solver| {
+        args.year.is_none_or(|year| solver.year() == year)
+            && args.day.is_none_or(|day| solver.day() == day)
+    });
+    let (run, errors) = bench::Run::measure(
+        bench::current_commit(),
+        solvers,
+        &args.generated.or(args.inputs.source()),
+        args.runs,
+    );
+    for e in &errors {
+        eprintln!("error: {:#}", e);
+    }
+    let comparisons = bench::compare(&run, &history);
+    let against = comparisons
+        .iter()
+        .filter_map(|comparison| comparison.against.as_deref())
+        .unique()
+        .join(", ");
+    println!(
+        "{:<4} {:>3} {:<6} {:>12} {:>12} {:>8}  (against {})",
+        "year",
+        "day",
+        "phase",
+        "time",
+        "baseline",
+        "change",
+        match against.is_empty() {
+            true => "nothing",
+            false => &against,
+        }
+    );
+    let threshold = args.threshold / 100.0;
+    let mut regressed = false;
+    for comparison in comparisons {
+        let timing = &comparison.timing;
+        let flag = match comparison.regressed(threshold) {
+            true => {
+                regressed = true;
+                "  regressed"
+            }
+            false => "",
+        };
+        println!(
+            "{:<4} {:>3} {:<6} {:>12} {:>12} {:>8}{}",
+            timing.year,
+            timing.day,
+            timing.phase.to_string(),
+            format!("{:.2?}", timing.runtime),
+            comparison
+                .baseline
+                .map_or(String::from("-"), |baseline| format!("{:.2?}", baseline)),
+            comparison
+                .change()
+                .map_or(String::from("-"), |change| format!(
+                    "{:+.1}%",
+                    change * 100.0
+                )),
+            flag
+        );
+    }
+    if !args.no_save && !generated {
+        history.record(run);
+        if let Err(e) = history.save(&args.history) {
+            eprintln!("error: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    match (regressed, errors.is_empty()) {
+        (true, _) => ExitCode::from(exit::REGRESSED),
+        (false, false) => ExitCode::from(exit::SOLVER_FAILED),
+        (false, true) => ExitCode::SUCCESS,
+    }
+}
+
+fn update_readme(args: Readme) -> ExitCode {
+    let history = args
+        .inputs
+        .input_dir
+        .clone()
+        .unwrap_or_else(input::default_directory);
+    let timings = match bench::History::load(&args.bench_history) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let rows = readme::rows(&args.inputs.source(), &history, &timings, args.runs);
+    let table = readme::render(&rows);
+    let result = fs::read_to_string("README.md")
+        .and_then(|existing| fs::write("README.md", readme::update(&existing, &table)));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: couldn't update README.md: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit_answer(submit: Submit) -> ExitCode {
+    let answer = match submit.answer {
+        Some(answer) => answer,
+        None => {
+            let solved = registry::find(submit.year, submit.day)
+                .with_context(|| format!("No solution for {} day {}", submit.year, submit.day))
+                .and_then(|solver| {
+                    let input = submit.inputs.source().load(submit.year, submit.day)?;
+                    solver.solve(submit.part, &input)
+                });
+            match solved {
+                Ok(answer) => answer,
+                Err(e) => {
+                    eprintln!("error: {:#}", e);
+                    return ExitCode::from(exit::SOLVER_FAILED);
+                }
+            }
+        }
+    };
+    println!("Submitting {}", answer);
+    let directory = submit
+        .inputs
+        .input_dir
+        .unwrap_or_else(input::default_directory);
+    let verdict = Client::from_env().and_then(|client| {
+        submit::submit(
+            &client,
+            &directory,
+            submit.year,
+            submit.day,
+            submit.part,
+            &answer,
+            SystemTime::now(),
+        )
+    });
+    match verdict {
+        Ok(Verdict::Correct) => {
+            println!("Correct!");
+            ExitCode::SUCCESS
+        }
+        Ok(Verdict::RateLimited { wait }) => {
+            eprintln!("Too soon, try again in {}s", wait.as_secs());
+            ExitCode::from(exit::TRY_LATER)
+        }
+        Ok(verdict) => {
+            eprintln!("Wrong: {:?}", verdict);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            match (e.downcast_ref(), e.downcast_ref()) {
+                (Some(Rejection::RateLimited(_)), _) => ExitCode::from(exit::TRY_LATER),
+                (_, Some(ClientError::SessionExpired)) => ExitCode::from(exit::NO_PERMISSION),
+                _ => ExitCode::FAILURE,
+            }
+        }
+    }
+}
+
+fn new_entry(new: New) -> ExitCode {
+    let result = scaffold::Entry::new(new.year, new.day, new.name)
+        .and_then(|entry| scaffold::scaffold(Path::new("."), &entry));
+    match result {
+        Ok(changed) if changed.is_empty() => println!("Nothing to do"),
+        Ok(changed) => changed.iter().for_each(|file| println!("Updated {}", file)),
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    // Not fatal, the input can be fetched later
+    let downloaded = Client::from_env()
+        .and_then(|client| client.fetch_cached(&input::default_directory(), new.year, new.day));
+    if let Err(e) = downloaded {
+        eprintln!("warning: couldn't download the input: {:#}", e);
+    }
+    ExitCode::SUCCESS
+}
+
+fn download_input(download: Download) -> ExitCode {
+    let directory = download.input_dir.unwrap_or_else(input::default_directory);
+    // Don't insist on a session cookie if there's nothing to do
+    let cached = input::path_in(&directory, download.year, download.day);
+    if cached.exists() {
+        println!("{}", cached.display());
+        return ExitCode::SUCCESS;
+    }
+    let result = Client::from_env()
+        .and_then(|client| client.fetch_cached(&directory, download.year, download.day));
+    match result {
+        Ok(path) => {
+            println!("{}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            download_failed(&e)
+        }
+    }
+}
+
+fn download_failed(e: &anyhow::Error) -> ExitCode {
+    match e.downcast_ref() {
+        Some(ClientError::SessionExpired) => ExitCode::from(exit::NO_PERMISSION),
+        Some(ClientError::NoSuchPuzzle { .. }) => ExitCode::from(exit::UNKNOWN_PUZZLE),
+        Some(ClientError::Locked { .. }) => ExitCode::from(exit::TRY_LATER),
+        Some(_) => ExitCode::from(exit::UNAVAILABLE),
+        None => ExitCode::FAILURE,
+    }
+}
+
+fn run_one(run: Run) -> ExitCode {
+    let year = run.year.unwrap_or(2021);
+    let day = run.day.expect("clap requires --day without --all");
+    let solver = match registry::find(year, day) {
+        Some(solver) => solver,
+        None => {
+            eprintln!("error: no solution for {} day {}", year, day);
+            return ExitCode::from(exit::UNKNOWN_PUZZLE);
+        }
+    };
+    let source = match run.input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => run.generated.or(run.inputs.source()),
+    };
+    if let (true, Some(part)) = (run.stream, run.part) {
+        return run_streaming(solver, &source, part);
+    }
+    let input = match source.load(year, day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::from(exit::NO_INPUT);
+        }
+    };
+    let parts = match run.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let solved = match solve_timed(solver, &input, &parts) {
+        Ok(solved) => solved,
+        Err(e) => {
+            match e.downcast_ref::<ParseError>() {
+                Some(parse_error) => eprintln!("{}", parse_error.render()),
+                None => eprintln!("error: couldn't parse input: {:#}", e),
+            }
+            return ExitCode::from(exit::PARSE_FAILED);
+        }
+    };
+    for (part, (result, _)) in parts.iter().zip(&solved.parts) {
+        match result {
+            Ok(answer) if run.part.is_some() => println!("{}", answer),
+            Ok(answer) => println!("part {}: {}", part, answer),
+            Err(e) => {
+                eprintln!("error: part {} failed: {:#}", part, e);
+                return ExitCode::from(exit::SOLVER_FAILED);
+            }
+        }
+    }
+    if run.part.is_none() {
+        println!(
+            "parse {:.2?}, part 1 {:.2?}, part 2 {:.2?}, total {:.2?}",
+            solved.parse,
+            solved.parts[0].1,
+            solved.parts[1].1,
+            solved.total()
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_streaming(solver: &dyn Solver, source: &InputSource, part: u8) -> ExitCode {
+    let (year, day) = (solver.year(), solver.day());
+    let mut reader = match source.reader(year, day) {
+        Ok(reader) => reader,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return ExitCode::from(exit::NO_INPUT);
+        }
+    };
+    match solver.stream(part, &mut reader) {
+        Some(Ok(answer)) => {
+            println!("{}", answer);
+            ExitCode::SUCCESS
+        }
+        Some(Err(e)) => match (
+            e.downcast_ref::<ParseError>(),
+            e.downcast_ref::<io::Error>(),
+        ) {
+            (Some(parse_error), _) => {
+                eprintln!("{}", parse_error.render());
+                ExitCode::from(exit::PARSE_FAILED)
+            }
+            (None, Some(_)) => {
+                eprintln!("error: {:#}", e);
+                ExitCode::from(exit::NO_INPUT)
+            }
+            (None, None) => {
+                eprintln!("error: part {} failed: {:#}", part, e);
+                ExitCode::from(exit::SOLVER_FAILED)
+            }
+        },
+        None => {
+            eprintln!(
+                "error: {} day {} part {} can't be streamed, run it without --stream",
+                year, day, part
+            );
+            ExitCode::from(exit::UNKNOWN_PUZZLE)
+        }
+    }
+}
+
+fn timed(f: impl FnOnce() -> anyhow::Result<String>) -> (anyhow::Result<String>, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Answers to some parts, with how long parsing and each part took
+struct Solved {
+    parse: Duration,
+    parts: Vec<(anyhow::Result<String>, Duration)>,
+}
+
+impl Solved {
+    fn total(&self) -> Duration {
+        self.parse
+            + self
+                .parts
+                .iter()
+                .map(|(_, elapsed)| *elapsed)
+                .sum::<Duration>()
+    }
+}
+
+/// Parse once, then solve the parts one after the other, so they aren't timed competing for CPU.
+/// Only fails if the input doesn't parse
+fn solve_timed(solver: &dyn Solver, input: &str, parts: &[u8]) -> anyhow::Result<Solved> {
+    let start = Instant::now();
+    let parsed = solver.parse(input)?;
+    let parse = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|part| timed(|| solver.solve_parsed(*part, &parsed)))
+        .collect();
+    Ok(Solved { parse, parts })
+}
+
+fn run_all(year: Option<u16>, source: InputSource, jobs: Option<NonZeroUsize>) -> ExitCode {
+    let pool = match rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.map_or(0, NonZeroUsize::get))
+        .build()
+    {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("error: couldn't start the thread pool: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let solvers = registry::all()
+        .filter(|solver| year.is_none_or(|year| solver.year() == year))
+        .collect::<Vec<_>>();
+    // In registry order, whichever finishes first
+    let reports = pool.install(|| {
+        solvers
+            .par_iter()
+            .map(|solver| {
+                let input = match source.load(solver.year(), solver.day()) {
+                    Ok(input) => input,
+                    Err(e) => return ("no input", Err(e)),
+                };
+                let start = Instant::now();
+                let parsed = match solver.parse(&input) {
+                    Ok(parsed) => parsed,
+                    Err(e) => return ("error", Err(e)),
+                };
+                let parse = start.elapsed();
+                // Both parts alongside the other days, since only the total is being watched here
+                let parts = [1, 2]
+                    .par_iter()
+                    .map(|part| timed(|| solver.solve_parsed(*part, &parsed)))
+                    .collect();
+                ("error", Ok(Solved { parse, parts }))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut failed = false;
+    println!(
+        "{:<4} {:>3}  {:<22} {:>12} {:>16} {:>12} {:>16} {:>12} {:>12}",
+        "year", "day", "name", "parse", "part 1", "time", "part 2", "time", "total"
+    );
+    for (solver, (failure, solved)) in solvers.into_iter().zip(reports) {
+        let (year, day) = (solver.year(), solver.day());
+        let cells = match solved {
+            Ok(solved) => {
+                let mut cells = vec![format!("{:.2?}", solved.parse)];
+                for (part, (result, elapsed)) in (1..).zip(&solved.parts) {
+                    match result {
+                        Ok(answer) => cells.extend([answer.clone(), format!("{:.2?}", elapsed)]),
+                        Err(e) => {
+                            failed = true;
+                            eprintln!("error: {} day {} part {}: {:#}", year, day, part, e);
+                            cells.extend([String::from("error"), String::from("-")]);
+                        }
+                    }
+                }
+                cells.push(format!("{:.2?}", solved.total()));
+                cells
+            }
+            Err(e) => {
+                failed = true;
+                eprintln!("error: {} day {}: {:#}", year, day, e);
+                let mut cells = vec![String::from("-"); 6];
+                cells[1] = String::from(failure);
+                cells
+            }
+        };
+        println!(
+            "{:<4} {:>3}  {:<22} {:>12} {:>16} {:>12} {:>16} {:>12} {:>12}",
+            year,
+            day,
+            solver.name(),
+            cells[0],
+            cells[1],
+            cells[2],
+            cells[3],
+            cells[4],
+            cells[5]
+        );
+    }
+    match failed {
+        true => ExitCode::from(exit::SOLVER_FAILED),
+        false => ExitCode::SUCCESS,
+    }
+}
+
+#[test]
+fn download_exit_codes() {
+    // Neither of these should reach the server
+    let client = Client::new("deadbeef").with_base_url("http://127.0.0.1:9");
+    let directory = std::env::temp_dir().join(format!("aoc-download-{}", std::process::id()));
+    let exit_code =
+        |year, day| download_failed(&client.fetch_cached(&directory, year, day).unwrap_err());
+    assert_eq!(exit_code(2099, 1), ExitCode::from(exit::TRY_LATER));
+    assert_eq!(exit_code(2021, 26), ExitCode::from(exit::UNKNOWN_PUZZLE));
+    assert!(!directory.exists());
+}
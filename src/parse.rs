@@ -0,0 +1,332 @@
+//! Errors for puzzle input that doesn't parse, pointing at exactly where it went wrong.
+//!
+//! Positions are found from the offending `&str` itself, which must be a slice of the input,
+//! so parsers can split and trim however they like and still report a location.
+//!
+//! The combinators split up the usual shapes of input, like [`csv_of`] or [`lines_of`],
+//! and hand each piece to a parser whose errors are re-located to point into the whole input.
+use std::{io::BufRead, str::FromStr};
+
+use anyhow::Context;
+use itertools::Either;
+
+use crate::utils::Grid;
+
+/// Lines and columns count from 1, columns are in characters
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display(
+    fmt = "line {}, column {}: expected {}, found {}",
+    line,
+    column,
+    expected,
+    "found(text)"
+)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, empty at the end of the input
+    pub text: String,
+    /// What should have been there, e.g `a number`
+    pub expected: String,
+    /// The whole line containing the error, for [`ParseError::render`]
+    source_line: String,
+}
+
+impl std::error::Error for ParseError {}
+
+fn found(text: &str) -> String {
+    match text.is_empty() {
+        true => String::from("nothing"),
+        false => format!("`{}`", text),
+    }
+}
+
+impl ParseError {
+    /// An error at a byte `offset` into `input`, covering `len` bytes
+    pub fn at(input: &str, offset: usize, len: usize, expected: impl Into<String>) -> Self {
+        let offset = floor_char_boundary(input, offset.min(input.len()));
+        let end = floor_char_boundary(input, (offset + len).min(input.len()));
+        let line_start = input[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |newline| offset + newline);
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: String::from(&input[offset..end.min(line_end).max(offset)]),
+            expected: expected.into(),
+            source_line: String::from(input[line_start..line_end].trim_end_matches('\r')),
+        }
+    }
+
+    /// An error covering `part`, which must be a slice of `input`
+    pub fn of(input: &str, part: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, offset_of(input, part), part.len(), expected)
+    }
+
+    /// The input stopped before something that was `expected`
+    pub fn end(input: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, input.trim_end().len(), 0, expected)
+    }
+
+    /// Re-locate an error from parsing `part` so it points into `input` instead
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let start = Self::at(input, offset_of(input, part), 0, "");
+        let line = start.line + self.line - 1;
+        let column = match self.line {
+            1 => start.column + self.column - 1,
+            _ => self.column,
+        };
+        let source_line = input.lines().nth(line - 1).unwrap_or_default();
+        Self {
+            line,
+            column,
+            source_line: String::from(source_line.trim_end_matches('\r')),
+            ..self
+        }
+    }
+
+    /// Re-number an error from parsing a single line, which is `line` of the input
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            line: line + self.line - 1,
+            ..self
+        }
+    }
+
+    /// Show the offending line, with a caret under the error
+    /// ```text
+    /// error: expected a number, found `1x`
+    ///  --> line 3, column 5
+    ///   |
+    /// 3 | 199 1x
+    ///   |     ^^
+    /// ```
+    pub fn render(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        // Keep tabs so the caret lines up
+        let indent = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| match c {
+                '\t' => '\t',
+                _ => ' ',
+            })
+            .collect::<String>();
+        format!(
+            "error: expected {}, found {}\n{} --> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self.expected,
+            found(&self.text),
+            gutter,
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            self.source_line,
+            gutter,
+            indent,
+            "^".repeat(self.text.chars().count().max(1)),
+        )
+    }
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Where `part` starts in `input`, or the end of `input` if it isn't a slice of it
+fn offset_of(input: &str, part: &str) -> usize {
+    (part.as_ptr() as usize)
+        .checked_sub(input.as_ptr() as usize)
+        .filter(|offset| offset + part.len() <= input.len())
+        .unwrap_or(input.len())
+}
+
+/// Parse `token`, a slice of `input`, reporting its position if it's invalid
+pub fn from_token<T: FromStr>(
+    input: &str,
+    token: &str,
+    expected: impl Into<String>,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::of(input, token, expected))
+}
+
+/// Parse a whole token with [`FromStr`], e.g `csv_of(input, token("a number"))`
+pub fn token<T: FromStr>(expected: &str) -> impl Fn(&str) -> Result<T, ParseError> + '_ {
+    move |token| from_token(token, token, expected)
+}
+
+/// The pieces of `input` between `separator`s, trimmed.
+/// A whitespace separator means any run of whitespace, like [`str::split_whitespace`].
+pub fn tokens<'a>(input: &'a str, separator: &'a str) -> impl Iterator<Item = &'a str> {
+    match separator.trim().is_empty() {
+        true => Either::Left(input.split_whitespace()),
+        false => Either::Right(input.trim().split(separator).map(str::trim)),
+    }
+}
+
+/// Parse every one of [`tokens`], so empty ones are errors unless `parse` allows them
+pub fn tokens_of<T>(
+    input: &str,
+    separator: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    tokens(input, separator)
+        .map(|token| parse(token).map_err(|e| e.within(input, token)))
+        .collect()
+}
+
+/// Comma separated, e.g `3,4,3,1,2`
+pub fn csv_of<T>(
+    input: &str,
+    parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    tokens_of(input, ",", parse)
+}
+
+/// One per line. Blank lines are passed to `parse` like any other
+pub fn lines_of<T>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Runs of lines separated by one or more blank lines, without their trailing newline
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut offset = 0;
+    input
+        .split_inclusive('\n')
+        .map(move |line| {
+            offset += line.len();
+            (offset - line.len(), line.trim().is_empty())
+        })
+        .chain([(input.len(), true)])
+        .scan(None, move |start, (offset, blank)| {
+            Some(match (*start, blank) {
+                (None, false) => {
+                    *start = Some(offset);
+                    None
+                }
+                (Some(from), true) => {
+                    *start = None;
+                    Some(input[from..offset].trim_end())
+                }
+                _ => None,
+            })
+        })
+        .flatten()
+}
+
+/// Parse each of [`blocks`], like the boards in a game of bingo
+pub fn blocks_of<T>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    blocks(input)
+        .map(|block| parse(block).map_err(|e| e.within(input, block)))
+        .collect()
+}
+
+/// A rectangle of single digits, e.g a height map
+pub fn digit_grid(input: &str, expected: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse_cells(input, expected, |c| c.to_digit(10))
+}
+
+/// Parse each line as it's read, so the input never has to be in memory all at once.
+/// Errors are numbered from the start of the input, as if it had all been read.
+pub fn lines_from<T>(
+    reader: impl BufRead,
+    mut parse: impl FnMut(&str) -> Result<T, ParseError>,
+) -> impl Iterator<Item = anyhow::Result<T>> {
+    reader.lines().enumerate().map(move |(index, line)| {
+        let line = line.with_context(|| format!("Couldn't read line {}", index + 1))?;
+        parse(&line).map_err(|e| e.on_line(index + 1).into())
+    })
+}
+
+#[test]
+fn points_at_the_error() {
+    let input = "199\n200\n2O8\n";
+    let line = input.lines().nth(2).unwrap();
+    let error = from_token::<usize>(input, line, "a depth").unwrap_err();
+    assert_eq!((error.line, error.column), (3, 1));
+    assert_eq!(
+        error.to_string(),
+        "line 3, column 1: expected a depth, found `2O8`"
+    );
+    assert_eq!(
+        error.render(),
+        "error: expected a depth, found `2O8`\n  --> line 3, column 1\n  |\n3 | 2O8\n  | ^^^"
+    );
+
+    let error = ParseError::end(input, "another depth");
+    assert_eq!((error.line, error.column, error.text.as_str()), (3, 4, ""));
+}
+
+#[test]
+fn relocates_errors() {
+    let input = "forward 5\ndown x\n";
+    let line = input.lines().nth(1).unwrap();
+    let error = ParseError::of(line, &line[5..], "a distance").within(input, line);
+    assert_eq!((error.line, error.column), (2, 6));
+    assert!(error.render().ends_with("2 | down x\n  |      ^"));
+}
+
+#[test]
+fn numbers_streamed_lines() {
+    let input = "199\n200\n2O8\n";
+    let parsed = lines_from(input.as_bytes(), |line| {
+        from_token::<u32>(line, line, "a depth")
+    })
+    .collect::<Vec<_>>();
+    assert_eq!(parsed.len(), 3);
+    let error = parsed[2].as_ref().unwrap_err().downcast_ref::<ParseError>();
+    let line = input.lines().nth(2).unwrap();
+    assert_eq!(
+        error,
+        Some(&from_token::<u32>(input, line, "a depth").unwrap_err())
+    );
+}
+
+#[test]
+fn combinators_point_into_the_input() {
+    assert_eq!(
+        tokens(" 1  2\t3\n", " ").collect::<Vec<_>>(),
+        ["1", "2", "3"]
+    );
+    assert_eq!(tokens("1, 2,3\n", ",").collect::<Vec<_>>(), ["1", "2", "3"]);
+
+    let input = "1,2\n3\n\n\n4 5\r\n6\r\n";
+    assert_eq!(blocks(input).collect::<Vec<_>>(), ["1,2\n3", "4 5\r\n6"]);
+    let error = blocks_of(input, |block| {
+        tokens_of(block, " ", token::<u8>("a number"))
+    })
+    .unwrap_err();
+    assert_eq!(
+        (error.line, error.column, error.text.as_str()),
+        (1, 1, "1,2")
+    );
+
+    let error = lines_of("1,2\n3,x\n", |line| csv_of::<u8>(line, token("a number"))).unwrap_err();
+    assert_eq!((error.line, error.column), (2, 3));
+    assert!(error.render().ends_with("2 | 3,x\n  |   ^"));
+    let error = csv_of::<u8>("1,,2", token("a number")).unwrap_err();
+    assert_eq!((error.line, error.column, error.text.as_str()), (1, 3, ""));
+
+    assert_eq!(
+        digit_grid("12\n34\n", "a digit").unwrap().row(1),
+        Some(&[3, 4][..])
+    );
+    let error = digit_grid("12\n3x\n", "a digit").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 2));
+}
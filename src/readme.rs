@@ -0,0 +1,252 @@
+//! Generating the table of puzzles in `README.md` from the [`registry`].
+//! Everything outside the markers is left alone.
+use std::{cell::LazyCell, path::Path, time::Duration};
+
+use itertools::Itertools;
+
+use crate::{
+    bench::{self, Phase},
+    input::InputSource,
+    registry,
+    solution::{Rating, Solver},
+    submit::History,
+};
+
+const START: &str = "<!-- puzzles: generated by `aoc readme`, edits will be overwritten -->";
+const END: &str = "<!-- /puzzles -->";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub name: String,
+    pub stars: u8,
+    pub rating: Option<Rating>,
+    /// Median runtimes, not counting parsing
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Row {
+    fn link(&self) -> String {
+        format!("[{}](./src/y{}/{}.rs)", self.name, self.year, self.name)
+    }
+
+    fn render(&self) -> String {
+        let stars = (0..2)
+            .map(|star| match star < self.stars {
+                true => '★',
+                false => '☆',
+            })
+            .collect::<String>();
+        let timing = |timing: Option<Duration>| match timing {
+            Some(timing) => format!("{:.1?}", timing),
+            None => String::from("-"),
+        };
+        format!(
+            "| {} | {:02} | {} | {} | {} | {} | {} |",
+            self.year,
+            self.day,
+            self.link(),
+            stars,
+            self.rating
+                .map(|rating| rating.to_string())
+                .unwrap_or_default(),
+            timing(self.part1),
+            timing(self.part2),
+        )
+    }
+
+    fn key(line: &str) -> Option<(u16, u8)> {
+        let mut cells = line.split('|').skip(1).map(str::trim);
+        Some((cells.next()?.parse().ok()?, cells.next()?.parse().ok()?))
+    }
+}
+
+/// Stars are whichever is higher of the solver's own count and the recorded correct answers.
+/// Timings are the latest from `aoc bench`, so they don't depend on how busy the machine is right now.
+/// Parts that were never benchmarked are timed as the median of `runs`, and missing if the input isn't available.
+pub fn row(
+    solver: &dyn Solver,
+    inputs: &InputSource,
+    history: &Path,
+    timings: &bench::History,
+    runs: usize,
+) -> Row {
+    let (year, day) = (solver.year(), solver.day());
+    let recorded = History::load(&History::path_in(history, year, day))
+        .map(|history| {
+            history
+                .parts
+                .values()
+                .filter(|part| part.correct().is_some())
+                .count() as u8
+        })
+        .unwrap_or(0);
+    let input = LazyCell::new(|| inputs.load(year, day).ok());
+    let timing = |phase| {
+        timings.latest(year, day, phase).or_else(|| {
+            input
+                .as_deref()
+                .and_then(|input| bench::median_runtime(solver, phase, input, runs).ok())
+        })
+    };
+    Row {
+        year,
+        day,
+        name: String::from(solver.name()),
+        stars: solver.stars().max(recorded),
+        rating: solver.rating(),
+        part1: timing(Phase::Part1),
+        part2: timing(Phase::Part2),
+    }
+}
+
+pub fn rows(
+    inputs: &InputSource,
+    history: &Path,
+    timings: &bench::History,
+    runs: usize,
+) -> Vec<Row> {
+    registry::all()
+        .map(|solver| row(solver, inputs, history, timings, runs))
+        .collect()
+}
+
+pub fn render(rows: &[Row]) -> String {
+    let mut lines = vec![
+        String::from(START),
+        String::from("| Year | Day | Puzzle | Stars | Rating | Part 1 | Part 2 |"),
+        String::from("| ---: | --: | ------ | ----- | ------ | -----: | -----: |"),
+    ];
+    lines.extend(rows.iter().map(Row::render));
+    lines.push(String::from(END));
+    lines.join("\n")
+}
+
+/// Replace the generated table, or the hand-written list it grew out of
+pub fn update(readme: &str, table: &str) -> String {
+    let lines = readme.lines().collect_vec();
+    let is_legacy = |line: &&str| line.starts_with("- ") && line.contains("day ");
+    let range = match (
+        lines.iter().position(|line| *line == START),
+        lines.iter().position(|line| *line == END),
+    ) {
+        (Some(start), Some(end)) if start < end => Some((start, end)),
+        _ => lines
+            .iter()
+            .position(is_legacy)
+            .zip(lines.iter().rposition(is_legacy)),
+    };
+    let updated = match range {
+        Some((start, end)) => lines[..start]
+            .iter()
+            .copied()
+            .chain(std::iter::once(table))
+            .chain(lines[end + 1..].iter().copied())
+            .join("\n"),
+        None => format!("{}\n\n{}", readme.trim_end(), table),
+    };
+    updated + "\n"
+}
+
+/// Add a row to an existing table, without regenerating the others
+pub fn insert_row(readme: &str, row: &Row) -> Option<String> {
+    if readme.contains(&row.link()) {
+        return None;
+    }
+    let mut lines = readme.lines().map(String::from).collect_vec();
+    let (start, end) = match (
+        lines.iter().position(|line| line == START),
+        lines.iter().position(|line| line == END),
+    ) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return Some(update(readme, &render(std::slice::from_ref(row)))),
+    };
+    let key = (row.year, row.day);
+    let index = (start + 1..end)
+        .find(|index| matches!(Row::key(&lines[*index]), Some(existing) if existing > key))
+        .unwrap_or(end);
+    lines.insert(index, row.render());
+    Some(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+fn example_row(day: u8, name: &str) -> Row {
+    Row {
+        year: 2021,
+        day,
+        name: String::from(name),
+        stars: 1,
+        rating: Some(Rating::Good),
+        part1: Some(Duration::from_micros(1500)),
+        part2: None,
+    }
+}
+
+#[test]
+fn preserves_prose() {
+    let readme = "# Advent of Code\n- 2021, day 01: [sonar_sweep](./src/sonar_sweep.rs) Interesting\n- 2021, day 02: [dive](./src/dive.rs)\n\n## Usage\nSome prose\n";
+    let table = render(&[example_row(1, "sonar_sweep")]);
+    let updated = update(readme, &table);
+    assert_eq!(
+        updated,
+        format!("# Advent of Code\n{}\n\n## Usage\nSome prose\n", table)
+    );
+    assert!(updated.contains(
+        "| 2021 | 01 | [sonar_sweep](./src/y2021/sonar_sweep.rs) | ★☆ | Good | 1.5ms | - |"
+    ));
+    // Regenerating replaces the table, and nothing else
+    assert_eq!(update(&updated, &table), updated);
+}
+
+#[test]
+fn inserts_rows_in_order() {
+    let readme = format!(
+        "# Advent of Code\n{}\nMore prose\n",
+        render(&[
+            example_row(1, "sonar_sweep"),
+            example_row(3, "binary_diagnostic")
+        ])
+    );
+    let inserted = insert_row(&readme, &example_row(2, "dive")).unwrap();
+    let days = inserted
+        .lines()
+        .filter_map(Row::key)
+        .map(|(_, day)| day)
+        .collect_vec();
+    assert_eq!(days, [1, 2, 3]);
+    assert!(inserted.ends_with("<!-- /puzzles -->\nMore prose\n"));
+    assert_eq!(insert_row(&inserted, &example_row(2, "dive")), None);
+}
+
+#[test]
+fn prefers_recorded_timings() {
+    let solver = registry::find(2021, 6).unwrap();
+    let timings = bench::History {
+        runs: vec![bench::Run {
+            commit: String::from("abc1234"),
+            timings: vec![bench::Timing {
+                year: 2021,
+                day: 6,
+                phase: Phase::Part1,
+                runtime: Duration::from_micros(1500),
+            }],
+        }],
+    };
+    // Part 1 was benchmarked, so its input is never needed
+    let missing = InputSource::Path("no-such-input.txt".into());
+    let recorded = row(solver, &missing, Path::new("no-such-history"), &timings, 1);
+    assert_eq!(
+        (recorded.part1, recorded.part2),
+        (Some(Duration::from_micros(1500)), None)
+    );
+    let unrecorded = row(
+        solver,
+        &missing,
+        Path::new("no-such-history"),
+        &bench::History::default(),
+        1,
+    );
+    assert_eq!((unrecorded.part1, unrecorded.part2), (None, None));
+}
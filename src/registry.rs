@@ -0,0 +1,39 @@
+//! Every solved puzzle, so harnesses don't have to know about individual modules.
+use crate::solution::Solver;
+
+/// Sorted by (year, day)
+static SOLVERS: &[&dyn Solver] = &[
+    &crate::y2021::sonar_sweep::SonarSweep,
+    &crate::y2021::dive::Dive,
+    &crate::y2021::binary_diagnostic::BinaryDiagnostic,
+    &crate::y2021::giant_squid::GiantSquid,
+    &crate::y2021::hydrothermal_venture::HydrothermalVenture,
+    &crate::y2021::lanternfish::Lanternfish,
+    &crate::y2021::treachery_of_whales::TreacheryOfWhales,
+    &crate::y2021::seven_segment_search::SevenSegmentSearch,
+    &crate::y2021::smoke_basin::SmokeBasin,
+    &crate::y2021::syntax_scoring::SyntaxScoring,
+];
+
+pub fn all() -> impl Iterator<Item = &'static dyn Solver> {
+    SOLVERS.iter().copied()
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static dyn Solver> {
+    all().find(|solver| solver.year() == year && solver.day() == day)
+}
+
+#[test]
+fn sorted_and_unique() {
+    let keys = all()
+        .map(|solver| (solver.year(), solver.day()))
+        .collect::<Vec<_>>();
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", keys);
+}
+
+#[test]
+fn lookup() {
+    let solver = find(2021, 7).expect("Day 7 is registered");
+    assert_eq!(solver.name(), "treachery_of_whales");
+    assert!(find(2021, 26).is_none());
+}
@@ -0,0 +1,412 @@
+//! Wiring up a new day, replacing `new-entry.bash` and the editor snippet.
+//! Every step checks whether it's already been done, so it's safe to re-run.
+use std::{fs, path::Path};
+
+use anyhow::{bail, ensure, Context};
+use itertools::Itertools;
+
+use crate::{client, fixture::Fixture, readme, registry};
+
+const TEMPLATE: &str = r#"use std::sync::LazyLock;
+
+use crate::{input, parse::lines_of, solution::Solution};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input({{year}}, {{day}}));
+static PARSED: LazyLock<Vec<String>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+fn parse(input: &str) -> anyhow::Result<Vec<String>> {
+    Ok(lines_of(input, |line| Ok(String::from(line)))?)
+}
+
+fn do_part1(_lines: &[String]) -> anyhow::Result<usize> {
+    Ok(0)
+}
+fn do_part2(_lines: &[String]) -> anyhow::Result<usize> {
+    Ok(0)
+}
+
+pub struct {{Name}};
+
+impl Solution for {{Name}} {
+    const YEAR: u16 = {{year}};
+    const DAY: u8 = {{day}};
+    const NAME: &'static str = "{{name}}";
+
+    type Parsed = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+}
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 0,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 0
+}
+"#;
+
+/// See [`crate::fixture`]
+const FIXTURE_TEMPLATE: &str = "# part1 = \n# part2 = \ninput = '''\n'''\n";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    /// The puzzle title in snake_case, which becomes the module name
+    pub name: String,
+}
+
+impl Entry {
+    pub fn new(year: u16, day: u8, name: impl Into<String>) -> anyhow::Result<Self> {
+        let name = name.into();
+        ensure!(year >= 2015, "Advent of Code started in 2015, not {}", year);
+        let last_day = client::last_day(year);
+        ensure!(
+            (1..=last_day).contains(&day),
+            "Day must be in 1..={} for {}, not {}",
+            last_day,
+            year,
+            day
+        );
+        ensure!(
+            name.starts_with(|c: char| c.is_ascii_lowercase())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            "Name must be a snake_case module name, not {:?}",
+            name
+        );
+        Ok(Self { year, day, name })
+    }
+
+    /// `treachery_of_whales` -> `TreacheryOfWhales`
+    pub fn type_name(&self) -> String {
+        self.name
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// The module holding every puzzle from the year, e.g `y2021`
+    pub fn year_module(&self) -> String {
+        format!("y{}", self.year)
+    }
+
+    fn module(&self) -> String {
+        TEMPLATE
+            .replace("{{year}}", &self.year.to_string())
+            .replace("{{day}}", &self.day.to_string())
+            .replace("{{name}}", &self.name)
+            .replace("{{Name}}", &self.type_name())
+    }
+}
+
+/// Write `contents` to `path`, unless it already has content
+fn write_new(path: &Path, contents: &str) -> anyhow::Result<bool> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    if !existing.trim().is_empty() {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Couldn't create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Couldn't write {}", path.display()))?;
+    Ok(true)
+}
+
+fn mod_name(line: &str) -> Option<&str> {
+    line.trim_start_matches("pub ")
+        .strip_prefix("mod ")?
+        .strip_suffix(';')
+}
+
+/// Declare `pub mod <name>;`, in alphabetical order
+fn declare_module(parent: &str, name: &str) -> Option<String> {
+    let declaration = format!("pub mod {};", name);
+    let mut lines = parent.lines().map(String::from).collect_vec();
+    match lines.iter().position(|line| mod_name(line) == Some(name)) {
+        Some(index) if lines[index] == declaration => return None,
+        // Private modules can't be registered
+        Some(index) => lines[index] = declaration,
+        None => {
+            let index = lines
+                .iter()
+                .position(|line| matches!(mod_name(line), Some(other) if other > name))
+                .or_else(|| {
+                    lines
+                        .iter()
+                        .rposition(|line| mod_name(line).is_some())
+                        .map(|last| last + 1)
+                })
+                .unwrap_or(lines.len());
+            lines.insert(index, declaration);
+        }
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+/// The (year, day, name) of each line of `SOLVERS` in `registry`, in order.
+/// This reads the files rather than the compiled [`registry`], so days added since the last build are seen.
+/// Days come from `const DAY` in each module, or the compiled registry if the module can't be read.
+fn registrations(src: &Path, registry: &str) -> anyhow::Result<Vec<(u16, u8, String)>> {
+    registry
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("&crate::y"))
+        .map(|path| {
+            let mut segments = path.split("::");
+            let (Some(year), Some(name)) = (segments.next(), segments.next()) else {
+                bail!("Can't read the registration of {:?}", path)
+            };
+            let year = year
+                .parse::<u16>()
+                .with_context(|| format!("Can't read the year of {:?}", path))?;
+            let module = src.join(format!("y{}", year)).join(format!("{}.rs", name));
+            let day = fs::read_to_string(module)
+                .ok()
+                .and_then(|module| {
+                    module.lines().find_map(|line| {
+                        line.trim()
+                            .strip_prefix("const DAY: u8 = ")?
+                            .strip_suffix(';')?
+                            .parse()
+                            .ok()
+                    })
+                })
+                .or_else(|| {
+                    registry::all()
+                        .find(|solver| solver.year() == year && solver.name() == name)
+                        .map(|solver| solver.day())
+                })
+                .with_context(|| format!("Can't tell which day {} is", path))?;
+            Ok((year, day, String::from(name)))
+        })
+        .collect()
+}
+
+/// Add the solver to `SOLVERS`, which is kept sorted by (year, day).
+/// `registered` is the (year, day) of each existing line, in order.
+fn register(
+    registry: &str,
+    registered: &[(u16, u8)],
+    entry: &Entry,
+) -> anyhow::Result<Option<String>> {
+    let registration = format!(
+        "    &crate::{}::{}::{},",
+        entry.year_module(),
+        entry.name,
+        entry.type_name()
+    );
+    if registry.lines().any(|line| line == registration) {
+        return Ok(None);
+    }
+    let mut lines = registry.lines().map(String::from).collect_vec();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("static SOLVERS"))
+        .context("No SOLVERS in registry")?
+        + 1;
+    let index = registered
+        .iter()
+        .position(|existing| *existing > (entry.year, entry.day))
+        .unwrap_or(registered.len());
+    lines.insert(start + index, registration);
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+fn update(
+    path: &Path,
+    f: impl FnOnce(&str) -> anyhow::Result<Option<String>>,
+) -> anyhow::Result<bool> {
+    let existing =
+        fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+    match f(&existing)? {
+        Some(updated) => {
+            fs::write(path, updated)
+                .with_context(|| format!("Couldn't write {}", path.display()))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Create and wire up the module for `entry` in the crate at `root`, returning the files that changed
+pub fn scaffold(root: &Path, entry: &Entry) -> anyhow::Result<Vec<String>> {
+    let src = root.join("src");
+    let registry_path = src.join("registry.rs");
+    let registrations = registrations(
+        &src,
+        &fs::read_to_string(&registry_path)
+            .with_context(|| format!("Couldn't read {}", registry_path.display()))?,
+    )?;
+    if let Some((_, _, existing)) = registrations
+        .iter()
+        .find(|(year, day, _)| (*year, *day) == (entry.year, entry.day))
+    {
+        ensure!(
+            *existing == entry.name,
+            "{} day {} is already registered as {}",
+            entry.year,
+            entry.day,
+            existing
+        );
+    }
+    let registered = registrations
+        .iter()
+        .map(|(year, day, _)| (*year, *day))
+        .collect_vec();
+
+    let year_module = entry.year_module();
+    let mut changed = Vec::new();
+    let module = format!("{}/{}.rs", year_module, entry.name);
+    if write_new(&src.join(&module), &entry.module())? {
+        changed.push(format!("src/{}", module));
+    }
+    // An empty fixture, filled in from the puzzle description
+    let fixture = Fixture::path_in(&src.join("inputs"), entry.year, entry.day);
+    if write_new(&fixture, FIXTURE_TEMPLATE)? {
+        changed.push(format!(
+            "src/inputs/{}/{}.example.toml",
+            entry.year, entry.day
+        ));
+    }
+    let parent = format!("{}/mod.rs", year_module);
+    write_new(
+        &src.join(&parent),
+        &format!("//! Puzzles from https://adventofcode.com/{}\n", entry.year),
+    )?;
+    if update(&src.join(&parent), |parent| {
+        Ok(declare_module(parent, &entry.name))
+    })? {
+        changed.push(format!("src/{}", parent));
+    }
+    if update(&src.join("lib.rs"), |lib| {
+        Ok(declare_module(lib, &year_module))
+    })? {
+        changed.push(String::from("src/lib.rs"));
+    }
+    if update(&registry_path, |registry| {
+        register(registry, &registered, entry)
+    })? {
+        changed.push(String::from("src/registry.rs"));
+    }
+    let row = readme::Row {
+        year: entry.year,
+        day: entry.day,
+        name: entry.name.clone(),
+        stars: 0,
+        rating: None,
+        part1: None,
+        part2: None,
+    };
+    if update(&root.join("README.md"), |readme| {
+        Ok(readme::insert_row(readme, &row))
+    })? {
+        changed.push(String::from("README.md"));
+    }
+    Ok(changed)
+}
+
+#[test]
+fn type_names() {
+    let entry = Entry::new(2021, 7, "treachery_of_whales").unwrap();
+    assert_eq!(entry.type_name(), "TreacheryOfWhales");
+    assert!(Entry::new(2021, 7, "Treachery").is_err());
+    assert!(Entry::new(2021, 26, "whales").is_err());
+    assert!(Entry::new(2025, 12, "whales").is_ok());
+    assert!(Entry::new(2025, 20, "whales").is_err());
+    assert!(Entry::new(2014, 7, "whales").is_err());
+    assert_eq!(
+        Entry::new(2021, 7, "whales").unwrap().year_module(),
+        "y2021"
+    );
+}
+
+#[test]
+fn scaffolding_is_idempotent() {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    fs::create_dir_all(root.join("src/y2021")).unwrap();
+    for file in [
+        "src/lib.rs",
+        "src/registry.rs",
+        "src/y2021/mod.rs",
+        "README.md",
+    ] {
+        fs::copy(file, root.join(file)).unwrap();
+    }
+    let entry = Entry::new(2021, 12, "passage_pathing").unwrap();
+
+    let changed = scaffold(&root, &entry).unwrap();
+    assert_eq!(
+        changed,
+        [
+            "src/y2021/passage_pathing.rs",
+            "src/inputs/2021/12.example.toml",
+            "src/y2021/mod.rs",
+            "src/registry.rs",
+            "README.md"
+        ]
+    );
+    let module = fs::read_to_string(root.join("src/y2021/passage_pathing.rs")).unwrap();
+    assert!(module.contains("impl Solution for PassagePathing"));
+    assert!(module.contains("input::puzzle_input(2021, 12)"));
+    let fixture = Fixture::load(&root.join("src/inputs/2021/12.example.toml")).unwrap();
+    assert_eq!(fixture.answer(1), None);
+
+    let year = fs::read_to_string(root.join("src/y2021/mod.rs")).unwrap();
+    assert!(year.contains("pub mod lanternfish;\npub mod passage_pathing;\n"));
+    let registry = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+    assert!(registry.contains(
+        "    &crate::y2021::syntax_scoring::SyntaxScoring,\n    &crate::y2021::passage_pathing::PassagePathing,\n"
+    ));
+    let readme = fs::read_to_string(root.join("README.md")).unwrap();
+    assert!(readme.contains(
+        "| 2021 | 12 | [passage_pathing](./src/y2021/passage_pathing.rs) | ☆☆ |  | - | - |\n<!-- /puzzles -->"
+    ));
+
+    // A new year gets its own module, so names can be reused
+    let entry = Entry::new(2015, 1, "sonar_sweep").unwrap();
+    let changed = scaffold(&root, &entry).unwrap();
+    assert!(changed.contains(&String::from("src/lib.rs")));
+    let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+    assert!(lib.contains("pub mod visualize;\npub mod y2015;\npub mod y2021;\n"));
+    let year = fs::read_to_string(root.join("src/y2015/mod.rs")).unwrap();
+    assert_eq!(
+        year,
+        "//! Puzzles from https://adventofcode.com/2015\npub mod sonar_sweep;\n"
+    );
+    let registry = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+    assert!(registry.contains(
+        "    &crate::y2015::sonar_sweep::SonarSweep,\n    &crate::y2021::sonar_sweep::SonarSweep,\n"
+    ));
+
+    assert!(scaffold(&root, &entry).unwrap().is_empty());
+    assert!(scaffold(&root, &Entry::new(2021, 1, "sonar_sweeping").unwrap()).is_err());
+
+    // Without a rebuild in between, so only the files know about day 12
+    scaffold(
+        &root,
+        &Entry::new(2021, 14, "extended_polymerization").unwrap(),
+    )
+    .unwrap();
+    let registry = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+    assert!(registry.contains(
+        "    &crate::y2021::passage_pathing::PassagePathing,\n    &crate::y2021::extended_polymerization::ExtendedPolymerization,\n"
+    ));
+    assert!(scaffold(&root, &Entry::new(2021, 12, "passages").unwrap()).is_err());
+    fs::remove_dir_all(root).unwrap();
+}
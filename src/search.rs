@@ -0,0 +1,324 @@
+//! Graph searches for puzzles about reachability and shortest paths,
+//! over anything implementing [`Graph`]: grids through [`GridGraph`], or state spaces through [`implicit`].
+//!
+//! Every search stops at the first node `goal` accepts, or explores everything reachable if it accepts none,
+//! and returns a [`Search`] with the order nodes were visited and how each was reached.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+use crate::utils::{Grid, Neighbourhood, Neighbouring};
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// Nodes one step away, with the cost of that step.
+    /// Breadth and depth first searches count steps and ignore the cost.
+    fn neighbours(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, usize)>;
+}
+
+/// A grid's cells, joined to their neighbours.
+/// `cost` is what it takes to step from one cell onto another, or [`None`] if you can't.
+pub struct GridGraph<'a, T, F> {
+    pub grid: &'a Grid<T>,
+    pub neighbourhood: Neighbourhood,
+    pub cost: F,
+}
+
+impl<T, F: Fn(&T, &T) -> Option<usize>> Graph for GridGraph<'_, T, F> {
+    type Node = (usize, usize);
+
+    fn neighbours(&self, &(row, column): &Self::Node) -> impl Iterator<Item = (Self::Node, usize)> {
+        let from = self.grid.get(row, column);
+        self.grid
+            .neighbours(row, column, self.neighbourhood)
+            .into_iter()
+            .flatten()
+            .filter_map(move |(row, column, to)| Some(((row, column), (self.cost)(from?, to)?)))
+    }
+}
+
+/// A graph that's only known by the steps out of each node, like the states of a puzzle
+pub struct Implicit<N, F> {
+    successors: F,
+    node: PhantomData<fn() -> N>,
+}
+
+/// See [`Implicit`]
+pub fn implicit<N, F, I>(successors: F) -> Implicit<N, F>
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    Implicit {
+        successors,
+        node: PhantomData,
+    }
+}
+
+impl<N, F, I> Graph for Implicit<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> impl Iterator<Item = (N, usize)> {
+        (self.successors)(node).into_iter()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Search<N> {
+    pub start: N,
+    /// The node `goal` accepted, if the search got that far
+    pub goal: Option<N>,
+    /// Every node visited, in the order they were visited
+    pub order: Vec<N>,
+    /// The node each was first reached from, or for Dijkstra and A* the cheapest.
+    /// The start has no parent.
+    pub parents: HashMap<N, N>,
+    /// How far each node is from the start, in steps or cost depending on the search
+    pub costs: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    fn new(start: N) -> Self {
+        Self {
+            goal: None,
+            order: Vec::new(),
+            parents: HashMap::new(),
+            costs: HashMap::from([(start.clone(), 0)]),
+            start,
+        }
+    }
+
+    /// From the start to `node`, or [`None`] if it was never reached
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.costs.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// From the start to the goal
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    pub fn cost(&self) -> Option<usize> {
+        self.costs.get(self.goal.as_ref()?).copied()
+    }
+}
+
+/// Nearest first, so the path to the goal has the fewest steps
+pub fn bfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut goal: impl FnMut(&G::Node) -> bool,
+) -> Search<G::Node> {
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        search.order.push(node.clone());
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let steps = search.costs[&node] + 1;
+        for (next, _) in graph.neighbours(&node) {
+            if !search.costs.contains_key(&next) {
+                search.costs.insert(next.clone(), steps);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Down each branch as far as it goes before backtracking.
+/// With an explicit stack, so deep graphs don't overflow the real one.
+pub fn dfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut goal: impl FnMut(&G::Node) -> bool,
+) -> Search<G::Node> {
+    let mut search = Search::new(start.clone());
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        search.order.push(node.clone());
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let steps = search.costs[&node] + 1;
+        for (next, _) in graph.neighbours(&node) {
+            if !visited.contains(&next) {
+                // The latest route is the one that'll be taken, since it's on top of the stack
+                search.costs.insert(next.clone(), steps);
+                search.parents.insert(next.clone(), node.clone());
+                stack.push(next);
+            }
+        }
+    }
+    search
+}
+
+/// Cheapest first, so the path to the goal costs the least
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    goal: impl FnMut(&G::Node) -> bool,
+) -> Search<G::Node> {
+    astar(graph, start, goal, |_| 0)
+}
+
+/// [`dijkstra`], guided towards the goal by a `heuristic` estimate of the remaining cost.
+/// Nodes are never expanded twice, so the path is only cheapest if the heuristic is consistent (monotone):
+/// it never drops by more than the cost of a step, like the Manhattan distance on a grid.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut goal: impl FnMut(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> usize,
+) -> Search<G::Node> {
+    let mut search = Search::new(start.clone());
+    let mut visited = HashSet::new();
+    // Nodes don't have to be Ord, so the queue holds indices into `nodes`
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut nodes = vec![start];
+    while let Some(Reverse((_, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        search.order.push(node.clone());
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let cost = search.costs[&node];
+        for (next, step) in graph.neighbours(&node) {
+            if visited.contains(&next) {
+                continue;
+            }
+            let cost = cost.saturating_add(step);
+            if search.costs.get(&next).is_none_or(|&known| cost < known) {
+                search.costs.insert(next.clone(), cost);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push(Reverse((
+                    cost.saturating_add(heuristic(&next)),
+                    nodes.len(),
+                )));
+                nodes.push(next);
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+fn chitons() -> Grid<u32> {
+    // The example from 2021 day 15
+    Grid::parse_digits(
+        "1163751742\n1381373672\n2136511328\n3694931569\n7463417111\n\
+         1319128137\n1359912421\n3125421639\n1293138521\n2311944581\n",
+    )
+    .unwrap()
+}
+
+#[test]
+fn cheapest_paths() {
+    let grid = chitons();
+    let graph = GridGraph {
+        grid: &grid,
+        neighbourhood: Neighbourhood::ORTHOGONAL,
+        cost: |_: &u32, to: &u32| Some(*to as usize),
+    };
+    let end = (grid.num_rows() - 1, grid.num_columns() - 1);
+    let dijkstra = dijkstra(&graph, (0, 0), |node| *node == end);
+    assert_eq!(dijkstra.cost(), Some(40));
+    let path = dijkstra.path().unwrap();
+    assert_eq!((path.first(), path.last()), (Some(&(0, 0)), Some(&end)));
+    let cost = path[1..].iter().map(|&cell| grid[cell] as usize).sum();
+    assert_eq!(dijkstra.cost(), Some(cost));
+
+    let manhattan = |&(row, column): &(usize, usize)| end.0 - row + end.1 - column;
+    let astar = astar(&graph, (0, 0), |node| *node == end, manhattan);
+    assert_eq!(astar.cost(), Some(40));
+    assert!(astar.order.len() <= dijkstra.order.len());
+    // Costs are only ever lowered before a node is expanded, so they all match their paths
+    for node in &astar.order {
+        let path = astar.path_to(node).unwrap();
+        let cost = path[1..].iter().map(|&cell| grid[cell] as usize).sum();
+        assert_eq!(astar.costs.get(node), Some(&cost));
+    }
+
+    let bfs = bfs(&graph, (0, 0), |node| *node == end);
+    assert_eq!(bfs.cost(), Some(18));
+    assert_eq!(bfs.path().unwrap().len(), 19);
+}
+
+#[test]
+fn reachability() {
+    // Walls of 9 split the grid in two
+    let grid = Grid::parse_digits("119\n199\n911\n").unwrap();
+    let graph = GridGraph {
+        grid: &grid,
+        neighbourhood: Neighbourhood::ORTHOGONAL,
+        cost: |_: &u32, to: &u32| (*to < 9).then_some(1),
+    };
+    let dfs = dfs(&graph, (0, 0), |_| false);
+    assert_eq!(dfs.goal, None);
+    assert_eq!(dfs.order.len(), 3);
+    assert_eq!(dfs.order[0], (0, 0));
+    assert_eq!(dfs.path_to(&(2, 2)), None);
+    assert_eq!(bfs(&graph, (2, 2), |_| false).order, [(2, 2), (2, 1)]);
+}
+
+#[test]
+fn implicit_graphs() {
+    // Fewest doublings and increments from 1 to 100
+    let graph = implicit(|&n: &u32| {
+        [(n * 2, 1), (n + 1, 1)]
+            .into_iter()
+            .filter(|(n, _)| *n <= 100)
+    });
+    let search = bfs(&graph, 1, |&n| n == 100);
+    assert_eq!(search.path().unwrap(), [1, 2, 3, 6, 12, 24, 25, 50, 100]);
+}
+
+#[test]
+fn expanded_nodes_keep_their_cost() {
+    // The heuristic overestimates at b, so a is expanded before the cheaper route through b is found
+    let graph = implicit(|node: &char| match node {
+        's' => vec![('a', 5), ('b', 1)],
+        'b' => vec![('a', 1)],
+        'a' => vec![('g', 1)],
+        _ => vec![],
+    });
+    let search = astar(
+        &graph,
+        's',
+        |_| false,
+        |node| match node {
+            'b' => 10,
+            _ => 0,
+        },
+    );
+    assert_eq!(search.order, ['s', 'a', 'g', 'b']);
+    assert_eq!((search.costs[&'a'], search.costs[&'g']), (5, 6));
+    assert_eq!(search.path_to(&'g').unwrap(), ['s', 'a', 'g']);
+}
@@ -0,0 +1,107 @@
+//! The common interface for every solved puzzle.
+use std::{any::Any, fmt::Display, io::BufRead};
+
+use anyhow::{bail, Context};
+
+/// A puzzle with a solver for each part.
+/// The input is parsed once, and both parts are solved from that, so each step can be timed separately.
+///
+/// Implemented on a unit struct in each day's module, e.g [`crate::y2021::sonar_sweep::SonarSweep`].
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    /// The module name, which matches the puzzle title
+    const NAME: &'static str;
+    /// How many parts have a confirmed answer
+    const STARS: u8 = 0;
+    /// How much I enjoyed the puzzle
+    const RATING: Option<Rating> = None;
+
+    /// Shared between threads when the parts run in parallel
+    type Parsed: Send + Sync + 'static;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1>;
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2>;
+
+    /// Solve a part line by line as the input is read, without holding all of it in memory.
+    /// [`None`] if the part needs the whole input, which is the default.
+    fn stream(_part: u8, _reader: &mut dyn BufRead) -> Option<anyhow::Result<String>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, derive_more::Display)]
+pub enum Rating {
+    Bad,
+    Average,
+    Good,
+    Interesting,
+}
+
+/// A [`Solution::Parsed`], which only the solver that parsed it can use
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// Object-safe version of [`Solution`], so different days can live side by side in the [`crate::registry`].
+pub trait Solver: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn stars(&self) -> u8;
+    fn rating(&self) -> Option<Rating>;
+    fn parse(&self, input: &str) -> anyhow::Result<Parsed>;
+    fn part1(&self, parsed: &Parsed) -> anyhow::Result<String>;
+    fn part2(&self, parsed: &Parsed) -> anyhow::Result<String>;
+    fn stream(&self, part: u8, reader: &mut dyn BufRead) -> Option<anyhow::Result<String>>;
+
+    fn solve_parsed(&self, part: u8, parsed: &Parsed) -> anyhow::Result<String> {
+        match part {
+            1 => self.part1(parsed),
+            2 => self.part2(parsed),
+            other => bail!("There is no part {}", other),
+        }
+    }
+
+    /// Parse the input, then solve the part
+    fn solve(&self, part: u8, input: &str) -> anyhow::Result<String> {
+        self.solve_parsed(part, &self.parse(input)?)
+    }
+}
+
+impl<T: Solution + Sync> Solver for T {
+    fn year(&self) -> u16 {
+        T::YEAR
+    }
+    fn day(&self) -> u8 {
+        T::DAY
+    }
+    fn name(&self) -> &'static str {
+        T::NAME
+    }
+    fn stars(&self) -> u8 {
+        T::STARS
+    }
+    fn rating(&self) -> Option<Rating> {
+        T::RATING
+    }
+    fn parse(&self, input: &str) -> anyhow::Result<Parsed> {
+        Ok(Box::new(T::parse(input)?))
+    }
+    fn part1(&self, parsed: &Parsed) -> anyhow::Result<String> {
+        T::part1(downcast::<T>(parsed)?).map(|answer| answer.to_string())
+    }
+    fn part2(&self, parsed: &Parsed) -> anyhow::Result<String> {
+        T::part2(downcast::<T>(parsed)?).map(|answer| answer.to_string())
+    }
+    fn stream(&self, part: u8, reader: &mut dyn BufRead) -> Option<anyhow::Result<String>> {
+        T::stream(part, reader)
+    }
+}
+
+fn downcast<T: Solution>(parsed: &Parsed) -> anyhow::Result<&T::Parsed> {
+    parsed
+        .downcast_ref()
+        .with_context(|| format!("Input wasn't parsed by {}", T::NAME))
+}
@@ -0,0 +1,374 @@
+//! Submitting answers, remembering what the site said so we never send the same wrong answer twice.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{into_body, Client, ClientError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint
+    Incorrect,
+    /// Submitted too soon after the last answer
+    RateLimited {
+        wait: Duration,
+    },
+    /// The site wouldn't accept an answer for this part, usually because it's already solved
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Read the site's response page
+    pub fn from_response(html: &str) -> Option<Self> {
+        let text = article_text(html);
+        let verdict = if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            match (text.contains("too high"), text.contains("too low")) {
+                (true, _) => Verdict::TooHigh,
+                (_, true) => Verdict::TooLow,
+                _ => Verdict::Incorrect,
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited {
+                wait: parse_wait(&text).unwrap_or(Duration::from_secs(60)),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            return None;
+        };
+        Some(verdict)
+    }
+}
+
+/// The site's message lives in the only `<article>`
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article)
+        .unwrap_or(html);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Understands "You have 1m 5s left to wait" and "Please wait one minute / N seconds before trying again"
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (amount, _) = rest.split_once(" left to wait")?;
+        let seconds = amount
+            .split_whitespace()
+            .map(
+                |token| match token.split_at(token.len().saturating_sub(1)) {
+                    (minutes, "m") => minutes.parse::<u64>().ok().map(|m| m * 60),
+                    (seconds, "s") => seconds.parse::<u64>().ok(),
+                    _ => None,
+                },
+            )
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(seconds));
+    }
+    let text = text.to_lowercase();
+    let (_, rest) = text.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+    let count = match words.next()? {
+        "one" | "a" | "an" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse().ok()?,
+    };
+    match words.next()? {
+        unit if unit.starts_with("second") => Some(Duration::from_secs(count)),
+        unit if unit.starts_with("minute") => Some(Duration::from_secs(count * 60)),
+        _ => None,
+    }
+}
+
+impl Client {
+    /// Post an answer, without consulting the [`History`].
+    /// Also returns how long the site wants us to wait before the next answer.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<(Verdict, Option<Duration>), ClientError> {
+        let response = self
+            .agent()
+            .post(&self.url(&format!("/{}/day/{}/answer", year, day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let body = into_body(response)?;
+        match Verdict::from_response(&body) {
+            Some(verdict) => Ok((verdict, parse_wait(&article_text(&body)))),
+            None => Err(ClientError::Unexpected { status: 200, body }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch
+    pub at: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartHistory {
+    pub submissions: Vec<Submission>,
+    /// Seconds since the unix epoch
+    pub next_allowed: Option<u64>,
+}
+
+/// Why an answer wasn't sent
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum Rejection {
+    #[display(fmt = "Already solved, the answer was {}", _0)]
+    AlreadySolved(String),
+    #[display(fmt = "Already submitted, and it was {:?}", _0)]
+    AlreadySubmitted(Verdict),
+    #[display(fmt = "{} is already known to be too high", _0)]
+    TooHigh(String),
+    #[display(fmt = "{} is already known to be too low", _0)]
+    TooLow(String),
+    #[display(fmt = "Too soon after the last answer, wait {}s", "_0.as_secs()")]
+    RateLimited(Duration),
+}
+
+impl std::error::Error for Rejection {}
+
+impl PartHistory {
+    pub fn correct(&self) -> Option<&str> {
+        self.submissions
+            .iter()
+            .find(|submission| submission.verdict == Verdict::Correct)
+            .map(|submission| submission.answer.as_str())
+    }
+
+    /// The lowest answer that was too high, and the highest that was too low
+    fn bounds(&self) -> (Option<i128>, Option<i128>) {
+        let numbers = |verdict: Verdict| {
+            self.submissions
+                .iter()
+                .filter(move |submission| submission.verdict == verdict)
+                .filter_map(|submission| submission.answer.trim().parse::<i128>().ok())
+        };
+        (
+            numbers(Verdict::TooHigh).min(),
+            numbers(Verdict::TooLow).max(),
+        )
+    }
+
+    /// Reject answers we already know are wrong, or that the site would refuse
+    pub fn check(&self, answer: &str, now: SystemTime) -> Result<(), Rejection> {
+        if let Some(correct) = self.correct() {
+            return Err(Rejection::AlreadySolved(String::from(correct)));
+        }
+        if let Some(previous) = self
+            .submissions
+            .iter()
+            .find(|submission| submission.answer == answer)
+        {
+            return Err(Rejection::AlreadySubmitted(previous.verdict.clone()));
+        }
+        if let Ok(candidate) = answer.trim().parse::<i128>() {
+            match self.bounds() {
+                (Some(too_high), _) if candidate >= too_high => {
+                    return Err(Rejection::TooHigh(String::from(answer)))
+                }
+                (_, Some(too_low)) if candidate <= too_low => {
+                    return Err(Rejection::TooLow(String::from(answer)))
+                }
+                _ => (),
+            }
+        }
+        let now = unix_seconds(now);
+        match self.next_allowed {
+            Some(next_allowed) if next_allowed > now => Err(Rejection::RateLimited(
+                Duration::from_secs(next_allowed - now),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// `wait` is the timeout the site imposed, which comes with wrong answers too
+    pub fn record(
+        &mut self,
+        answer: &str,
+        verdict: Verdict,
+        wait: Option<Duration>,
+        now: SystemTime,
+    ) {
+        let now = unix_seconds(now);
+        self.next_allowed = wait.map(|wait| now + wait.as_secs());
+        if !matches!(verdict, Verdict::RateLimited { .. } | Verdict::WrongLevel) {
+            self.submissions.push(Submission {
+                answer: String::from(answer),
+                verdict,
+                at: now,
+            })
+        }
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Every submission for a day, keyed by part
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub parts: BTreeMap<u8, PartHistory>,
+}
+
+impl History {
+    /// Kept next to the input, as `<directory>/<year>/<day>.answers.json`
+    pub fn path_in(directory: &Path, year: u16, day: u8) -> PathBuf {
+        directory
+            .join(year.to_string())
+            .join(format!("{}.answers.json", day))
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .with_context(|| format!("Corrupt answer history in {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n").with_context(|| format!("Couldn't write {}", path.display()))
+    }
+
+    pub fn part(&mut self, part: u8) -> &mut PartHistory {
+        self.parts.entry(part).or_default()
+    }
+}
+
+/// Check the answer against the history in `directory`, then submit and record it
+pub fn submit(
+    client: &Client,
+    directory: &Path,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+    now: SystemTime,
+) -> anyhow::Result<Verdict> {
+    let path = History::path_in(directory, year, day);
+    let mut history = History::load(&path)?;
+    history.part(part).check(answer, now)?;
+
+    let (verdict, wait) = client.submit(year, day, part, answer)?;
+    history
+        .part(part)
+        .record(answer, verdict.clone(), wait, now);
+    history.save(&path)?;
+    Ok(verdict)
+}
+
+#[test]
+fn reads_responses() {
+    let too_high = "<main><article><p>That's not the right answer; your answer is too high.  \
+        If you're stuck, make sure you're using the full input data. \
+        Please wait one minute before trying again. <a href=\"/2021/day/7\">[Return to Day 7]</a></p></article></main>";
+    assert_eq!(Verdict::from_response(too_high), Some(Verdict::TooHigh));
+    assert_eq!(
+        parse_wait(&article_text(too_high)),
+        Some(Duration::from_secs(60))
+    );
+
+    let rate_limited = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. </p></article>";
+    assert_eq!(
+        Verdict::from_response(rate_limited),
+        Some(Verdict::RateLimited {
+            wait: Duration::from_secs(65)
+        })
+    );
+    assert_eq!(
+        Verdict::from_response(
+            "<article><p>That's the right answer!  You are one gold star closer.</p></article>"
+        ),
+        Some(Verdict::Correct)
+    );
+    assert_eq!(
+        parse_wait("please wait 30 seconds before trying again"),
+        Some(Duration::from_secs(30))
+    );
+}
+
+#[test]
+fn guards_against_known_wrong_answers() {
+    let directory = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+    let (base_url, server) = crate::client::stub_server(vec![
+        (
+            200,
+            "<article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article>",
+        ),
+        (200, "<article><p>That's the right answer!</p></article>"),
+    ]);
+    let client = Client::new("deadbeef").with_base_url(base_url);
+    let start = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let submit_at = |answer, now| {
+        submit(&client, &directory, 2021, 7, 1, answer, now)
+            .map_err(|e| e.downcast::<Rejection>().unwrap())
+    };
+
+    assert_eq!(submit_at("100", start), Ok(Verdict::TooHigh));
+    assert_eq!(
+        submit_at("50", start + Duration::from_secs(10)),
+        Err(Rejection::RateLimited(Duration::from_secs(50)))
+    );
+    let later = start + Duration::from_secs(120);
+    assert_eq!(
+        submit_at("100", later),
+        Err(Rejection::AlreadySubmitted(Verdict::TooHigh))
+    );
+    assert_eq!(
+        submit_at("150", later),
+        Err(Rejection::TooHigh(String::from("150")))
+    );
+    assert_eq!(submit_at("50", later), Ok(Verdict::Correct));
+    assert_eq!(
+        submit_at("51", later),
+        Err(Rejection::AlreadySolved(String::from("50")))
+    );
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].starts_with("POST /2021/day/7/answer "));
+    assert!(requests[0].ends_with("level=1&answer=100"));
+    fs::remove_dir_all(directory).unwrap();
+}
@@ -1,6 +1,51 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    ops::{Index, IndexMut},
+    slice,
+};
 
-use array2d::Array2D;
+use crate::parse::{self, ParseError};
+
+/// A test asserting each answer, and a benchmark of each computation.
+/// The benchmarks need `cargo +nightly bench --features nightly`.
+/// ```ignore
+/// crate::utils::benchtest! {
+///     part1: do_part1(black_box(&PARSED)).unwrap() => 1316
+/// }
+/// ```
+macro_rules! benchtest {
+    ($($name:ident: $compute:expr => $ans:expr),* $(,)?) => {
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            #[allow(unused_imports)]
+            use std::hint::black_box;
+            $(
+                #[test]
+                fn $name() {
+                    assert_eq!($compute, $ans);
+                }
+            )*
+        }
+
+        #[cfg(all(test, feature = "nightly"))]
+        mod benches {
+            use super::*;
+            #[allow(unused_imports)]
+            use std::hint::black_box;
+            extern crate test;
+            $(
+                #[bench]
+                fn $name(b: &mut test::Bencher) {
+                    b.iter(|| $compute);
+                }
+            )*
+        }
+    };
+}
+pub(crate) use benchtest;
 
 pub trait CountOccurences<T> {
     fn count_occurences(self) -> HashMap<T, usize>;
@@ -20,44 +65,581 @@ where
     }
 }
 
-pub struct Neighbours<'a, T: Clone> {
-    array: &'a Array2D<T>,
-    row: usize,
-    column: usize,
+/// A rectangle of cells, stored row by row and indexed by `(row, column)` from the top left
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    num_rows: usize,
+    num_columns: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// [`None`] unless there are exactly `num_rows * num_columns` cells
+    pub fn from_row_major(cells: Vec<T>, num_rows: usize, num_columns: usize) -> Option<Self> {
+        match num_rows.checked_mul(num_columns) == Some(cells.len()) {
+            true => Some(Self {
+                num_rows,
+                num_columns,
+                cells,
+            }),
+            false => None,
+        }
+    }
+
+    /// [`None`] if the rows aren't all the same length
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Option<Self> {
+        let mut rows = rows.into_iter().peekable();
+        let num_columns = rows.peek().map_or(0, Vec::len);
+        let mut num_rows = 0;
+        let mut cells = Vec::new();
+        for row in rows {
+            if row.len() != num_columns {
+                return None;
+            }
+            cells.extend(row);
+            num_rows += 1;
+        }
+        Some(Self {
+            num_rows,
+            num_columns,
+            cells,
+        })
+    }
+
+    pub fn from_fn(
+        num_rows: usize,
+        num_columns: usize,
+        mut cell: impl FnMut(usize, usize) -> T,
+    ) -> Self {
+        let cells = (0..num_rows)
+            .flat_map(|row| (0..num_columns).map(move |column| (row, column)))
+            .map(|(row, column)| cell(row, column))
+            .collect();
+        Self {
+            num_rows,
+            num_columns,
+            cells,
+        }
+    }
+
+    pub fn filled(value: T, num_rows: usize, num_columns: usize) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(num_rows, num_columns, |_, _| value.clone())
+    }
+
+    /// One row per line and one cell per character, like the maps in puzzle inputs.
+    /// A character `cell` can't make sense of is an error `expected` that, as are blank lines and rows of different lengths.
+    pub fn parse_cells(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut num_rows = 0;
+        let mut cells = Vec::new();
+        let mut num_columns = None;
+        for line in input.lines() {
+            if line.is_empty() {
+                return Err(ParseError::of(input, line, expected));
+            }
+            let start = cells.len();
+            for (i, c) in line.char_indices() {
+                let parsed = cell(c)
+                    .ok_or_else(|| ParseError::of(input, &line[i..i + c.len_utf8()], expected))?;
+                cells.push(parsed);
+            }
+            match num_columns {
+                None => num_columns = Some(cells.len() - start),
+                Some(num_columns) if cells.len() - start != num_columns => {
+                    return Err(ParseError::of(
+                        input,
+                        line,
+                        format!("a row of {} cells", num_columns),
+                    ))
+                }
+                Some(_) => (),
+            }
+            num_rows += 1;
+        }
+        Ok(Self {
+            num_rows,
+            num_columns: num_columns.unwrap_or_default(),
+            cells,
+        })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        match row < self.num_rows && column < self.num_columns {
+            true => self.cells.get(row * self.num_columns + column),
+            false => None,
+        }
+    }
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut T> {
+        match row < self.num_rows && column < self.num_columns {
+            true => self.cells.get_mut(row * self.num_columns + column),
+            false => None,
+        }
+    }
+
+    /// Off one edge and back on at the opposite one, so every index is in the grid.
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, row: isize, column: isize) -> &T {
+        let (row, column) = self.wrap(row, column);
+        &self[(row, column)]
+    }
+    pub fn get_wrapping_mut(&mut self, row: isize, column: isize) -> &mut T {
+        let (row, column) = self.wrap(row, column);
+        &mut self[(row, column)]
+    }
+    fn wrap(&self, row: isize, column: isize) -> (usize, usize) {
+        assert!(!self.is_empty(), "Can't wrap around an empty grid");
+        (
+            row.rem_euclid(self.num_rows as isize) as usize,
+            column.rem_euclid(self.num_columns as isize) as usize,
+        )
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        match row < self.num_rows {
+            true => Some(&self.cells[row * self.num_columns..(row + 1) * self.num_columns]),
+            false => None,
+        }
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.num_rows)
+            .map(|row| &self.cells[row * self.num_columns..(row + 1) * self.num_columns])
+    }
+
+    pub fn column(&self, column: usize) -> Option<impl Iterator<Item = &T>> {
+        match column < self.num_columns {
+            true => Some((0..self.num_rows).map(move |row| &self[(row, column)])),
+            false => None,
+        }
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.num_columns)
+            .map(move |column| (0..self.num_rows).map(move |row| &self[(row, column)]))
+    }
+
+    /// Row by row
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every `(row, column)`, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let num_columns = self.num_columns;
+        (0..self.num_rows).flat_map(move |row| (0..num_columns).map(move |column| (row, column)))
+    }
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            num_rows: self.num_rows,
+            num_columns: self.num_columns,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Rows become columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.num_columns, self.num_rows, |row, column| {
+            self[(column, row)].clone()
+        })
+    }
+}
+
+impl Grid<u32> {
+    /// See [`parse::digit_grid`]
+    pub fn parse_digits(input: &str) -> Result<Self, ParseError> {
+        parse::digit_grid(input, "a digit")
+    }
+}
+
+impl Grid<char> {
+    pub fn parse_chars(input: &str) -> Result<Self, ParseError> {
+        Self::parse_cells(input, "a character", Some)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        self.get(row, column).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside a grid of {} by {}",
+                row, column, self.num_rows, self.num_columns
+            )
+        })
+    }
 }
 
-pub struct NeighboursMut<'a, T: Clone> {
-    array: &'a mut Array2D<T>,
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        let (num_rows, num_columns) = (self.num_rows, self.num_columns);
+        self.get_mut(row, column).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside a grid of {} by {}",
+                row, column, num_rows, num_columns
+            )
+        })
+    }
+}
+
+impl<T> IntoIterator for Grid<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Row by row
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A line per row, with the cells run together like a puzzle input
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn grid_round_trips() {
+    let input = "123\n456\n";
+    let grid = Grid::parse_digits(input).unwrap();
+    assert_eq!((grid.num_rows(), grid.num_columns()), (2, 3));
+    assert_eq!(grid.to_string(), input);
+    assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+    assert_eq!(grid.column(2).unwrap().collect::<Vec<_>>(), [&3, &6]);
+    assert_eq!(grid.get(2, 0), None);
+    assert_eq!(*grid.get_wrapping(-1, 3), 4);
+    assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+    assert_eq!(Grid::from_rows([vec![1, 2], vec![3]]), None);
+    assert_eq!(Grid::from_row_major(vec![1, 2, 3], 2, 2), None);
+}
+
+#[test]
+fn grid_errors_point_at_the_cell() {
+    let error = Grid::parse_digits("123\n4x6\n").unwrap_err();
+    assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+    let error = Grid::parse_digits("123\n45\n").unwrap_err();
+    assert_eq!(
+        (error.line, error.expected.as_str()),
+        (2, "a row of 3 cells")
+    );
+    let error = Grid::parse_chars("ab\n\nab\n").unwrap_err();
+    assert_eq!(error.line, 2);
+}
+
+/// Off a bounded edge there's nothing, off a toroidal one you come back on at the opposite edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Edges {
+    #[default]
+    Bounded,
+    Toroidal,
+}
+
+/// Which cells around a cell are its neighbours, as `(row, column)` offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighbourhood {
+    pub offsets: &'static [(isize, isize)],
+    pub edges: Edges,
+}
+
+impl Neighbourhood {
+    /// Up, left, right and down
+    pub const ORTHOGONAL: Self = Self::stencil(&[(-1, 0), (0, -1), (0, 1), (1, 0)]);
+    /// All eight cells around, including the orthogonal ones
+    pub const DIAGONAL: Self = Self::stencil(&[
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ]);
+
+    /// Any shape, e.g a knight's moves, with bounded edges
+    pub const fn stencil(offsets: &'static [(isize, isize)]) -> Self {
+        Self {
+            offsets,
+            edges: Edges::Bounded,
+        }
+    }
+
+    pub const fn toroidal(self) -> Self {
+        Self {
+            edges: Edges::Toroidal,
+            ..self
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// `(row, column)` moved by `(rows, columns)`, or [`None`] if that goes off a bounded edge
+    pub fn offset(
+        &self,
+        (row, column): (usize, usize),
+        (rows, columns): (isize, isize),
+        edges: Edges,
+    ) -> Option<(usize, usize)> {
+        match edges {
+            Edges::Bounded => {
+                let row = row.checked_add_signed(rows)?;
+                let column = column.checked_add_signed(columns)?;
+                self.get(row, column).map(|_| (row, column))
+            }
+            Edges::Toroidal if self.is_empty() => None,
+            Edges::Toroidal => Some(self.wrap(
+                (row as isize).wrapping_add(rows),
+                (column as isize).wrapping_add(columns),
+            )),
+        }
+    }
+}
+
+/// `(row, column, cell)` for each neighbour, in the order of the [`Neighbourhood`]'s offsets.
+/// On a small toroidal grid, offsets can wrap round to the same cell, which is then yielded more than once.
+pub struct Neighbours<'a, T> {
+    grid: &'a Grid<T>,
     row: usize,
     column: usize,
+    edges: Edges,
+    offsets: slice::Iter<'static, (isize, isize)>,
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offsets.find_map(|&offset| {
+            let (row, column) = self
+                .grid
+                .offset((self.row, self.column), offset, self.edges)?;
+            Some((row, column, &self.grid[(row, column)]))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.offsets.size_hint().1)
+    }
 }
 
-struct Foo<T>(T);
+/// `(row, column, cell)` for each neighbour, in the order of the [`Neighbourhood`]'s offsets.
+/// Unlike [`Neighbours`], a cell that several offsets wrap round to is only yielded the first time,
+/// since it can only be borrowed mutably once.
+pub struct NeighboursMut<'a, T> {
+    cells: std::vec::IntoIter<(usize, usize, &'a mut T)>,
+}
+
+impl<'a, T> Iterator for NeighboursMut<'a, T> {
+    type Item = (usize, usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cells.next()
+    }
 
-impl Foo<&mut u8> {}
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
 
-// TODO update smoke_basin to use this
-pub trait Neighbouring<T: Clone> {
-    fn neighbours(&self, row: usize, column: usize) -> Option<Neighbours<T>>;
-    fn neighbours_mut(&mut self, row: usize, column: usize) -> Option<NeighboursMut<T>>;
+pub trait Neighbouring<T> {
+    /// [`None`] if `(row, column)` isn't in the grid
+    fn neighbours(
+        &self,
+        row: usize,
+        column: usize,
+        neighbourhood: Neighbourhood,
+    ) -> Option<Neighbours<'_, T>>;
+    fn neighbours_mut(
+        &mut self,
+        row: usize,
+        column: usize,
+        neighbourhood: Neighbourhood,
+    ) -> Option<NeighboursMut<'_, T>>;
 }
 
-impl<T: Clone> Neighbouring<T> for Array2D<T> {
-    fn neighbours(&self, row: usize, column: usize) -> Option<Neighbours<T>> {
+impl<T> Neighbouring<T> for Grid<T> {
+    fn neighbours(
+        &self,
+        row: usize,
+        column: usize,
+        neighbourhood: Neighbourhood,
+    ) -> Option<Neighbours<'_, T>> {
         self.get(row, column)?;
         Some(Neighbours {
-            array: self,
+            grid: self,
             row,
             column,
+            edges: neighbourhood.edges,
+            offsets: neighbourhood.offsets.iter(),
         })
     }
 
-    fn neighbours_mut(&mut self, row: usize, column: usize) -> Option<NeighboursMut<T>> {
+    fn neighbours_mut(
+        &mut self,
+        row: usize,
+        column: usize,
+        neighbourhood: Neighbourhood,
+    ) -> Option<NeighboursMut<'_, T>> {
         self.get(row, column)?;
+        let mut order = Vec::with_capacity(neighbourhood.offsets.len());
+        for &offset in neighbourhood.offsets {
+            if let Some((row, column)) = self.offset((row, column), offset, neighbourhood.edges) {
+                let index = row * self.num_columns + column;
+                if !order.contains(&index) {
+                    order.push(index);
+                }
+            }
+        }
+        // Split the cells off front to back, then put them back in offset order
+        let mut by_index = order.into_iter().enumerate().collect::<Vec<_>>();
+        by_index.sort_unstable_by_key(|&(_, index)| index);
+        let mut rest = &mut self.cells[..];
+        let mut start = 0;
+        let mut cells = Vec::with_capacity(by_index.len());
+        for (position, index) in by_index {
+            let (cell, tail) = std::mem::take(&mut rest)[index - start..]
+                .split_first_mut()
+                .expect("offsets stay on the grid");
+            rest = tail;
+            start = index + 1;
+            cells.push((position, index, cell));
+        }
+        cells.sort_unstable_by_key(|&(position, _, _)| position);
+        let num_columns = self.num_columns;
         Some(NeighboursMut {
-            array: self,
-            row,
-            column,
+            cells: cells
+                .into_iter()
+                .map(|(_, index, cell)| (index / num_columns, index % num_columns, cell))
+                .collect::<Vec<_>>()
+                .into_iter(),
         })
     }
 }
+
+#[test]
+fn neighbours_stay_on_the_grid() {
+    let mut grid = Grid::parse_digits("123\n456\n789\n").unwrap();
+    let around = |grid: &Grid<u32>, row, column, neighbourhood| {
+        grid.neighbours(row, column, neighbourhood)
+            .unwrap()
+            .map(|(_, _, cell)| *cell)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(around(&grid, 0, 0, Neighbourhood::ORTHOGONAL), [2, 4]);
+    assert_eq!(
+        around(&grid, 1, 1, Neighbourhood::DIAGONAL),
+        [1, 2, 3, 4, 6, 7, 8, 9]
+    );
+    assert_eq!(
+        around(&grid, 0, 0, Neighbourhood::ORTHOGONAL.toroidal()),
+        [7, 3, 2, 4]
+    );
+    const KNIGHT: Neighbourhood = Neighbourhood::stencil(&[(1, 2), (2, 1), (-1, 2)]);
+    assert_eq!(around(&grid, 0, 0, KNIGHT), [6, 8]);
+    assert!(grid.neighbours(3, 0, Neighbourhood::ORTHOGONAL).is_none());
+
+    for (_, _, cell) in grid
+        .neighbours_mut(0, 1, Neighbourhood::DIAGONAL.toroidal())
+        .unwrap()
+    {
+        *cell = 0;
+    }
+    // Wrapping round a 3 by 3 grid, every other cell is a neighbour
+    assert_eq!(grid.to_string(), "020\n000\n000\n");
+
+    let mut grid = Grid::parse_digits("12\n").unwrap();
+    let positions = grid
+        .neighbours_mut(0, 0, Neighbourhood::DIAGONAL.toroidal())
+        .unwrap()
+        .map(|(row, column, _)| (row, column))
+        .collect::<Vec<_>>();
+    assert_eq!(positions, [(0, 1), (0, 0)]);
+}
+
+#[test]
+fn neighbours_mut_match_neighbours() {
+    use itertools::Itertools;
+
+    const STENCIL: Neighbourhood = Neighbourhood::stencil(&[(2, 1), (-1, -1), (0, 3), (1, 0)]);
+    let mut grid = Grid::parse_digits(
+        "1234
+5678
+9012
+",
+    )
+    .unwrap();
+    for (row, column) in [(0, 0), (1, 2), (2, 3)] {
+        let shared = grid
+            .neighbours(row, column, STENCIL.toroidal())
+            .unwrap()
+            .map(|(row, column, cell)| (row, column, *cell))
+            .collect::<Vec<_>>();
+        let mutable = grid
+            .neighbours_mut(row, column, STENCIL.toroidal())
+            .unwrap()
+            .map(|(row, column, cell)| (row, column, *cell));
+        assert_eq!(shared.len(), STENCIL.offsets.len());
+        for (shared, mutable) in shared.iter().zip_eq(mutable) {
+            assert_eq!(*shared, mutable);
+        }
+    }
+
+    // Where offsets wrap round to the same cell, only the first is kept
+    let mut grid = Grid::parse_digits(
+        "12
+",
+    )
+    .unwrap();
+    let shared = grid
+        .neighbours(0, 0, Neighbourhood::DIAGONAL.toroidal())
+        .unwrap()
+        .map(|(row, column, _)| (row, column))
+        .unique()
+        .collect::<Vec<_>>();
+    let mutable = grid
+        .neighbours_mut(0, 0, Neighbourhood::DIAGONAL.toroidal())
+        .unwrap()
+        .map(|(row, column, _)| (row, column))
+        .collect::<Vec<_>>();
+    assert_eq!(shared, mutable);
+}
@@ -0,0 +1,329 @@
+//! Pictures of puzzle state, for debugging solutions by eye.
+//!
+//! A grid of values is coloured with a [`ColourMap`], anything interesting is drawn on top with [`Image::overlay`],
+//! and the result is written as PPM, PNG, or coloured terminal art through [`Display`](fmt::Display).
+//! Days list what they can draw in [`PICTURES`], for `aoc draw`.
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{bail, Context};
+use num::ToPrimitive;
+
+use crate::{
+    utils::Grid,
+    y2021::{hydrothermal_venture, smoke_basin},
+};
+
+/// Larger than any real puzzle needs, but small enough to allocate without trouble
+pub const MAX_PIXELS: usize = 4096 * 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+    pub const RED: Self = Self(255, 0, 0);
+
+    /// `amount` of the way from `self` to `other`
+    pub fn mix(self, other: Self, amount: f64) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let channel = |from: u8, to: u8| {
+            (f64::from(from) + (f64::from(to) - f64::from(from)) * amount).round() as u8
+        };
+        Self(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    /// A colour for labelling the `index`th region, unlike the ones for nearby indices
+    pub fn distinct(index: usize) -> Self {
+        // Stepping round by the golden angle never lands on the same hue twice
+        let hue = (index as f64 * 137.508) % 360.0;
+        let (saturation, value) = (0.7, 0.95);
+        let chroma = value * saturation;
+        let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u32 / 60 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let channel = |c: f64| ((c + value - chroma) * 255.0).round() as u8;
+        Self(channel(r), channel(g), channel(b))
+    }
+}
+
+/// How values are turned into colours, from the smallest to the largest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColourMap {
+    Greyscale,
+    /// Black through red and yellow to white, for counts
+    Heat,
+    /// Deep water up to high ground, for heights
+    Terrain,
+}
+
+impl ColourMap {
+    fn stops(self) -> &'static [Rgb] {
+        match self {
+            ColourMap::Greyscale => &[Rgb::BLACK, Rgb::WHITE],
+            ColourMap::Heat => &[Rgb::BLACK, Rgb(200, 0, 0), Rgb(255, 220, 0), Rgb::WHITE],
+            ColourMap::Terrain => &[
+                Rgb(10, 20, 90),
+                Rgb(30, 110, 170),
+                Rgb(70, 160, 80),
+                Rgb(170, 140, 90),
+                Rgb(240, 240, 240),
+            ],
+        }
+    }
+
+    /// The colour `fraction` of the way along the map, between 0 and 1
+    pub fn colour(self, fraction: f64) -> Rgb {
+        let stops = self.stops();
+        let position = fraction.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let index = (position as usize).min(stops.len() - 2);
+        stops[index].mix(stops[index + 1], position - index as f64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    /// Row major
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// A pixel per cell, coloured by where its value falls between the smallest and largest in the grid
+    pub fn from_grid<T: ToPrimitive>(grid: &Grid<T>, map: ColourMap) -> Self {
+        let values = grid
+            .iter()
+            .map(|value| value.to_f64().unwrap_or_default())
+            .collect::<Vec<_>>();
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Self {
+            width: grid.num_columns(),
+            height: grid.num_rows(),
+            pixels: values
+                .into_iter()
+                .map(|value| match max > min {
+                    true => map.colour((value - min) / (max - min)),
+                    false => map.colour(0.0),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<Rgb> {
+        match row < self.height && column < self.width {
+            true => Some(self.pixels[row * self.width + column]),
+            false => None,
+        }
+    }
+
+    /// Blend `colour` over the pixels at each `(row, column)`, hiding what's underneath at an `opacity` of 1.
+    /// Points outside the image are ignored.
+    pub fn overlay(
+        &mut self,
+        points: impl IntoIterator<Item = (usize, usize)>,
+        colour: Rgb,
+        opacity: f64,
+    ) -> &mut Self {
+        for (row, column) in points {
+            if row < self.height && column < self.width {
+                let pixel = &mut self.pixels[row * self.width + column];
+                *pixel = pixel.mix(colour, opacity);
+            }
+        }
+        self
+    }
+
+    /// Each pixel becomes a `factor` by `factor` square, since puzzle grids make for tiny images.
+    /// Fails if that would be more than [`MAX_PIXELS`]
+    pub fn scale(&self, factor: usize) -> anyhow::Result<Self> {
+        let size = self
+            .width
+            .checked_mul(factor)
+            .zip(self.height.checked_mul(factor))
+            .filter(|(width, height)| {
+                width
+                    .checked_mul(*height)
+                    .is_some_and(|pixels| pixels <= MAX_PIXELS)
+            });
+        let Some((width, height)) = size else {
+            bail!(
+                "{} by {} scaled by {} is too big to draw",
+                self.width,
+                self.height,
+                factor
+            )
+        };
+        let pixels = (0..height)
+            .flat_map(|row| (0..width).map(move |column| (row, column)))
+            .map(|(row, column)| self.pixels[row / factor * self.width + column / factor])
+            .collect();
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub(crate) fn bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+            .collect()
+    }
+
+    /// Binary PPM, which needs no encoder and opens almost anywhere
+    pub fn write_ppm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.bytes())
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> anyhow::Result<()> {
+        let mut encoder =
+            png::Encoder::new(writer, self.width.try_into()?, self.height.try_into()?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.bytes())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// As PNG or PPM, depending on the extension
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        if !matches!(extension, Some("png" | "ppm")) {
+            bail!(
+                "Can't tell what format {} should be, use .png or .ppm",
+                path.display()
+            )
+        }
+        let file =
+            File::create(path).with_context(|| format!("Couldn't create {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        match extension {
+            Some("png") => self.write_png(&mut writer)?,
+            _ => self.write_ppm(&mut writer)?,
+        }
+        writer
+            .flush()
+            .with_context(|| format!("Couldn't write {}", path.display()))
+    }
+}
+
+/// Terminal art in 24 bit colour, with two pixels to a character so the picture isn't stretched
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in (0..self.height).step_by(2) {
+            for column in 0..self.width {
+                let Rgb(r, g, b) = self.pixels[row * self.width + column];
+                write!(f, "\x1b[38;2;{};{};{}m", r, g, b)?;
+                match self.get(row + 1, column) {
+                    Some(Rgb(r, g, b)) => write!(f, "\x1b[48;2;{};{};{}m▀", r, g, b)?,
+                    None => write!(f, "\x1b[49m▀")?,
+                }
+            }
+            writeln!(f, "\x1b[0m")?;
+        }
+        Ok(())
+    }
+}
+
+/// Something a day can draw from its input
+#[derive(Debug, Clone, Copy)]
+pub struct Picture {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    pub draw: fn(&str) -> anyhow::Result<Image>,
+}
+
+static PICTURES: &[&[Picture]] = &[&hydrothermal_venture::PICTURES, &smoke_basin::PICTURES];
+
+pub fn all() -> impl Iterator<Item = &'static Picture> {
+    PICTURES.iter().flat_map(|pictures| pictures.iter())
+}
+
+/// Every picture of a day, in the order they were listed
+pub fn of(year: u16, day: u8) -> impl Iterator<Item = &'static Picture> {
+    all().filter(move |picture| picture.year == year && picture.day == day)
+}
+
+#[test]
+fn colour_maps_span_their_stops() {
+    for map in [ColourMap::Greyscale, ColourMap::Heat, ColourMap::Terrain] {
+        assert_eq!(map.colour(0.0), map.stops()[0]);
+        assert_eq!(map.colour(1.0), *map.stops().last().unwrap());
+        assert_eq!(map.colour(2.0), map.colour(1.0));
+    }
+    assert_eq!(ColourMap::Greyscale.colour(0.5), Rgb(128, 128, 128));
+}
+
+#[test]
+fn writes_images() {
+    let grid = Grid::from_rows([vec![0, 1], vec![2, 3], vec![4, 5]]).unwrap();
+    let mut image = Image::from_grid(&grid, ColourMap::Greyscale);
+    image.overlay([(0, 1), (9, 9)], Rgb::RED, 1.0);
+    assert_eq!(image.get(0, 0), Some(Rgb::BLACK));
+    assert_eq!(image.get(0, 1), Some(Rgb::RED));
+    assert_eq!(image.get(2, 1), Some(Rgb::WHITE));
+
+    let mut ppm = Vec::new();
+    image.write_ppm(&mut ppm).unwrap();
+    assert!(ppm.starts_with(b"P6\n2 3\n255\n"));
+    assert_eq!(ppm.len(), 11 + 2 * 3 * 3);
+
+    let mut encoded = Vec::new();
+    image.scale(2).unwrap().write_png(&mut encoded).unwrap();
+    let mut reader = png::Decoder::new(io::Cursor::new(encoded))
+        .read_info()
+        .unwrap();
+    let mut decoded = vec![0; reader.output_buffer_size().unwrap()];
+    reader.next_frame(&mut decoded).unwrap();
+    assert_eq!(decoded, image.scale(2).unwrap().bytes());
+    assert!(image.scale(usize::MAX).is_err());
+    assert!(image.scale(4096).is_err());
+
+    // Two rows of pixels to a line, with the odd one out on a line of its own
+    assert_eq!(image.to_string().lines().count(), 2);
+}
+
+#[test]
+fn draws_every_picture() {
+    for picture in all() {
+        let input = crate::input::puzzle_input(picture.year, picture.day);
+        let image = (picture.draw)(&input).unwrap();
+        assert!(image.width() > 0 && image.height() > 0, "{}", picture.name);
+    }
+}
@@ -0,0 +1,242 @@
+/// There must be a better way...
+/// ## Next time...
+/// - Try a derivable enum BitCount { Zero(usize) }...
+///   As a kind of perfect hashmap, maybe with a nice entry api!
+/// - Use fallible functions everywhere
+/// ## Missed tricks
+/// - gamma = bit-flipped epsilon
+/// - whatever the hell Chris P is doing https://github.com/ThePants999/advent-of-code-2021/blob/main/src/day3.rs
+use anyhow::{anyhow, ensure, Context};
+use itertools::Itertools;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::LazyLock,
+};
+
+use crate::{
+    input,
+    parse::ParseError,
+    solution::{Rating, Solution},
+    utils::Grid,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bit {
+    Zero,
+    One,
+}
+
+impl TryFrom<char> for Bit {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '0' => Ok(Self::Zero),
+            '1' => Ok(Self::One),
+            _ => Err(anyhow!("Invalid bit: {}", value)),
+        }
+    }
+}
+
+impl From<&Bit> for char {
+    fn from(b: &Bit) -> Self {
+        match b {
+            Bit::Zero => '0',
+            Bit::One => '1',
+        }
+    }
+}
+
+fn make_array(s: &str) -> Result<Grid<Bit>, ParseError> {
+    Grid::parse_cells(s, "0 or 1", |c| Bit::try_from(c).ok())
+}
+
+fn counts<T: Hash + Eq>(it: impl IntoIterator<Item = T>) -> HashMap<T, usize> {
+    it.into_iter().fold(HashMap::new(), |mut counts, el| {
+        counts
+            .entry(el)
+            .and_modify(|count| *count += 1)
+            .or_default();
+        counts
+    })
+}
+
+fn most_common<T: Hash + Eq>(it: impl IntoIterator<Item = T>) -> Option<T> {
+    counts(it)
+        .into_iter()
+        .reduce(|winner, challenger| match winner.1 > challenger.1 {
+            true => winner,
+            false => challenger,
+        })
+        .map(|(t, _count)| t)
+}
+
+fn least_common<T: Hash + Eq>(it: impl IntoIterator<Item = T>) -> Option<T> {
+    counts(it)
+        .into_iter()
+        .reduce(|winner, challenger| match winner.1 < challenger.1 {
+            true => winner,
+            false => challenger,
+        })
+        .map(|(t, _count)| t)
+}
+
+fn part1_generic(
+    input: &Grid<Bit>,
+    mut select_bit_from_column: impl FnMut(Vec<&Bit>) -> Option<&Bit>, // Can't use fn(impl Iterator)...?
+) -> anyhow::Result<usize> {
+    let s = input
+        .columns()
+        .map(|column| select_bit_from_column(column.collect_vec()).map(char::from))
+        .collect::<Option<String>>()
+        .context("Empty column")?;
+    usize::from_str_radix(&s, 2).with_context(|| format!("Rate {:?} doesn't fit in a usize", s))
+}
+
+fn gamma_rate(input: &Grid<Bit>) -> anyhow::Result<usize> {
+    part1_generic(input, |v| most_common(v))
+}
+
+fn epsilon_rate(input: &Grid<Bit>) -> anyhow::Result<usize> {
+    part1_generic(input, |v| least_common(v))
+}
+
+// Well that was a lot of wasted work!
+// Let's just go imperative
+fn part2_generic(
+    input: &Grid<Bit>,
+    preferrer: impl Fn(HashMap<Bit, usize>) -> Bit,
+) -> anyhow::Result<usize> {
+    let mut possible = input.rows().map(<[Bit]>::to_vec).collect::<HashSet<_>>();
+
+    for i in 0..input.num_columns() {
+        if possible.len() == 1 {
+            break;
+        }
+
+        let counts = counts(possible.iter().map(|s| s[i]));
+        // Everything left agrees, so there's nothing to filter on
+        if counts.len() == 1 {
+            continue;
+        }
+        let preferred = preferrer(counts);
+        possible.retain(|s| s[i] == preferred);
+    }
+    ensure!(
+        possible.len() == 1,
+        "Expected one rating to remain, not {}",
+        possible.len()
+    );
+    let rating = possible
+        .drain()
+        .next()
+        .context("No rating remains")?
+        .iter()
+        .map(char::from)
+        .collect::<String>();
+    usize::from_str_radix(&rating, 2)
+        .with_context(|| format!("Rating {:?} doesn't fit in a usize", rating))
+}
+
+fn oxygen_generator_rating(input: &Grid<Bit>) -> anyhow::Result<usize> {
+    part2_generic(input, |counts| {
+        let num_zeroes = counts.get(&Bit::Zero).unwrap_or(&0);
+        let num_ones = counts.get(&Bit::One).unwrap_or(&0);
+        match num_zeroes.cmp(num_ones) {
+            Ordering::Less => Bit::One,
+            Ordering::Equal => Bit::One,
+            Ordering::Greater => Bit::Zero,
+        }
+    })
+}
+
+fn co2_scrubber_rating(input: &Grid<Bit>) -> anyhow::Result<usize> {
+    part2_generic(input, |counts| {
+        let num_zeroes = counts.get(&Bit::Zero).unwrap_or(&0);
+        let num_ones = counts.get(&Bit::One).unwrap_or(&0);
+        match num_zeroes.cmp(num_ones) {
+            Ordering::Less => Bit::Zero,
+            Ordering::Equal => Bit::Zero,
+            Ordering::Greater => Bit::One,
+        }
+    })
+}
+
+#[test]
+fn example() {
+    let s = "\
+        00100\n\
+        11110\n\
+        10110\n\
+        10111\n\
+        10101\n\
+        01111\n\
+        00111\n\
+        11100\n\
+        10000\n\
+        11001\n\
+        00010\n\
+        01010";
+    let input = make_array(s).unwrap();
+    let rating = oxygen_generator_rating(&input).unwrap();
+    assert_eq!(rating, 23);
+
+    let rating = co2_scrubber_rating(&input).unwrap();
+    assert_eq!(rating, 10);
+}
+
+#[test]
+fn ratings_skip_unanimous_bits() {
+    let input = make_array("001\n011\n").unwrap();
+    assert_eq!(oxygen_generator_rating(&input).unwrap(), 0b011);
+    assert_eq!(co2_scrubber_rating(&input).unwrap(), 0b001);
+}
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 3));
+static PARSED: LazyLock<Grid<Bit>> = LazyLock::new(|| make_array(&INPUT).unwrap());
+
+fn do_part1(input: &Grid<Bit>) -> anyhow::Result<usize> {
+    let epsilon = epsilon_rate(input)?;
+    let gamma = gamma_rate(input)?;
+    epsilon
+        .checked_mul(gamma)
+        .context("Power consumption overflowed")
+}
+fn do_part2(input: &Grid<Bit>) -> anyhow::Result<usize> {
+    let o2_rating = oxygen_generator_rating(input)?;
+    let co2_rating = co2_scrubber_rating(input)?;
+    o2_rating
+        .checked_mul(co2_rating)
+        .context("Life support rating overflowed")
+}
+
+pub struct BinaryDiagnostic;
+
+impl Solution for BinaryDiagnostic {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 3;
+    const NAME: &'static str = "binary_diagnostic";
+    const STARS: u8 = 2;
+    const RATING: Option<Rating> = Some(Rating::Bad);
+
+    type Parsed = Grid<Bit>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(make_array(input)?)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+}
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 4139586,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 1800151
+}
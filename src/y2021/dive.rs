@@ -0,0 +1,166 @@
+use anyhow::Context;
+use std::{io::BufRead, str::FromStr, sync::LazyLock};
+
+use crate::{
+    input,
+    parse::{from_token, lines_from, lines_of, tokens, ParseError},
+    solution::{Rating, Solution},
+};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 2));
+static PARSED: LazyLock<Vec<DiveInstruction>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+/// One instruction per line, parsed as it's read
+pub fn instructions(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<DiveInstruction>> {
+    lines_from(reader, str::parse)
+}
+
+fn parse(input: &str) -> anyhow::Result<Vec<DiveInstruction>> {
+    Ok(lines_of(input, str::parse)?)
+}
+
+fn do_part1(instructions: &[DiveInstruction]) -> anyhow::Result<usize> {
+    follow_course(instructions.iter().copied().map(Ok))?.product()
+}
+fn do_part2(instructions: &[DiveInstruction]) -> anyhow::Result<usize> {
+    follow_course_aim(instructions.iter().copied().map(Ok))?.product()
+}
+
+pub struct Dive;
+
+impl Solution for Dive {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 2;
+    const NAME: &'static str = "dive";
+    const STARS: u8 = 2;
+    const RATING: Option<Rating> = Some(Rating::Interesting);
+
+    type Parsed = Vec<DiveInstruction>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+    fn stream(part: u8, reader: &mut dyn BufRead) -> Option<anyhow::Result<String>> {
+        let position = match part {
+            1 => follow_course(instructions(reader)),
+            2 => follow_course_aim(instructions(reader)),
+            _ => return None,
+        };
+        Some(
+            position
+                .and_then(|position| position.product())
+                .map(|answer| answer.to_string()),
+        )
+    }
+}
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 1524750,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 1592426537,
+    part1_streaming: Dive::stream(1, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "1524750",
+    part2_streaming: Dive::stream(2, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "1592426537"
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DiveInstruction {
+    Forward(usize),
+    Up(usize),
+    Down(usize),
+}
+
+impl FromStr for DiveInstruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut split = tokens(s, " ");
+        let word = split
+            .next()
+            .ok_or_else(|| ParseError::end(s, "an instruction"))?;
+        let num = split
+            .next()
+            .ok_or_else(|| ParseError::end(s, "a distance"))
+            .and_then(|num| from_token(s, num, "a distance"))?;
+        if let Some(extra) = split.next() {
+            return Err(ParseError::of(s, extra, "the end of the line"));
+        }
+        let dive_instruction = match word {
+            "forward" => Self::Forward(num),
+            "up" => Self::Up(num),
+            "down" => Self::Down(num),
+            other => return Err(ParseError::of(s, other, "forward, up or down")),
+        };
+        Ok(dive_instruction)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Position {
+    horizontal: usize,
+    depth: usize,
+}
+
+impl Position {
+    fn product(&self) -> anyhow::Result<usize> {
+        self.horizontal
+            .checked_mul(self.depth)
+            .context("Position is too far away")
+    }
+}
+
+/// The submarine can't go above the surface, or further than a `usize`.
+/// Instructions are fallible so they can be followed as they're read.
+fn follow_course(
+    input: impl IntoIterator<Item = anyhow::Result<DiveInstruction>>,
+) -> anyhow::Result<Position> {
+    input
+        .into_iter()
+        .try_fold(Position::default(), |mut pos, instruction| {
+            match instruction? {
+                DiveInstruction::Forward(by) => {
+                    pos.horizontal = pos.horizontal.checked_add(by).context("Too far")?
+                }
+                DiveInstruction::Up(by) => {
+                    pos.depth = pos.depth.checked_sub(by).context("Above the surface")?
+                }
+                DiveInstruction::Down(by) => {
+                    pos.depth = pos.depth.checked_add(by).context("Too deep")?
+                }
+            }
+            Ok(pos)
+        })
+}
+
+fn follow_course_aim(
+    input: impl IntoIterator<Item = anyhow::Result<DiveInstruction>>,
+) -> anyhow::Result<Position> {
+    let (_, pos) = input.into_iter().try_fold(
+        (0usize, Position::default()),
+        |(mut aim, mut pos), instruction| {
+            match instruction? {
+                DiveInstruction::Forward(by) => {
+                    pos.horizontal = pos.horizontal.checked_add(by).context("Too far")?;
+                    pos.depth = aim
+                        .checked_mul(by)
+                        .and_then(|descent| pos.depth.checked_add(descent))
+                        .context("Too deep")?;
+                }
+                DiveInstruction::Up(by) => {
+                    aim = aim.checked_sub(by).context("Aiming above the surface")?
+                }
+                DiveInstruction::Down(by) => {
+                    aim = aim.checked_add(by).context("Aiming too deep")?
+                }
+            };
+            Ok::<_, anyhow::Error>((aim, pos))
+        },
+    )?;
+    Ok(pos)
+}
@@ -0,0 +1,312 @@
+/// # Next time
+/// - Use a set for each row, column, so you can just test for membership
+use anyhow::{ensure, Context};
+use itertools::{zip, Itertools};
+use std::{
+    collections::VecDeque,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+use crate::{
+    animate::{Animation, Recorder, Recording},
+    input,
+    parse::{blocks_of, csv_of, from_token, token, tokens, ParseError},
+    solution::{Rating, Solution},
+    utils::Grid,
+    visualize::{Image, Rgb, MAX_PIXELS},
+};
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    current_draw: u8,
+    future_draws: VecDeque<u8>,
+    boards: Vec<Board>,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (draws, rest) = s.split_once('\n').unwrap_or((s, &s[s.len()..]));
+        if draws.trim().is_empty() {
+            return Err(ParseError::of(s, draws, "a line of draws"));
+        }
+        let mut future_draws =
+            VecDeque::from(csv_of(draws, token("a draw")).map_err(|e| e.within(s, draws))?);
+        let mut boards = blocks_of(rest, str::parse::<Board>).map_err(|e| e.within(s, rest))?;
+
+        // Do first round
+        let current_draw = future_draws
+            .pop_front()
+            .ok_or_else(|| ParseError::end(s, "a draw"))?;
+        for board in boards.iter_mut() {
+            board.mark_at(current_draw)
+        }
+
+        Ok(Game {
+            current_draw,
+            future_draws,
+            boards,
+        })
+    }
+}
+
+impl Iterator for Game {
+    type Item = (Board, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pos) = self.boards.iter().position(Board::winner) {
+            return Some((self.boards.swap_remove(pos), self.current_draw));
+        }
+        while self.draw_next() {
+            if let Some(pos) = self.boards.iter().position(Board::winner) {
+                return Some((self.boards.swap_remove(pos), self.current_draw));
+            }
+        }
+        None
+    }
+}
+
+impl Game {
+    /// Mark the next number on every board, or return `false` if there are none left
+    fn draw_next(&mut self) -> bool {
+        match self.future_draws.pop_front() {
+            Some(draw) => {
+                self.current_draw = draw;
+                for board in self.boards.iter_mut() {
+                    board.mark_at(self.current_draw)
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Board {
+    array: Grid<Mark<u8>>,
+}
+
+impl FromStr for Board {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers = tokens(s, " ").collect_vec();
+        let lin = numbers
+            .iter()
+            .map(|number| from_token(s, number, "a number on the board"))
+            .map_ok(Mark::unmarked)
+            .collect::<Result<Vec<_>, _>>()?;
+        let array = Grid::from_row_major(lin, 5, 5).ok_or_else(|| match numbers.get(25) {
+            Some(extra) => ParseError::of(s, extra, "5 rows of 5 numbers"),
+            None => ParseError::end(s, "5 rows of 5 numbers"),
+        })?;
+        Ok(Board { array })
+    }
+}
+
+impl Board {
+    fn winner(&self) -> bool {
+        let winning_column = self
+            .array
+            .columns()
+            .any(|mut column| column.all(Mark::is_marked));
+
+        let winning_row = self.array.rows().any(|row| row.iter().all(Mark::is_marked));
+
+        // diagonals don't count 🤦
+        // ^-_
+        let _descending = (0..5).map(|co| &self.array[(co, co)]).all(Mark::is_marked);
+
+        // _-^
+        let _ascending = zip(0..5, (0..5).rev())
+            .map(|(row, column)| &self.array[(row, column)])
+            .all(Mark::is_marked);
+
+        winning_column || winning_row
+    }
+
+    fn sum_unmarked(self) -> usize {
+        self.array
+            .into_iter()
+            .filter_map(|mark| match mark {
+                Mark::Marked(_) => None,
+                Mark::Unmarked(t) => Some(t as usize),
+            })
+            .sum()
+    }
+
+    fn mark_at(&mut self, value: u8) {
+        for element in self.array.iter_mut() {
+            if **element == value {
+                element.mark()
+            }
+        }
+    }
+}
+
+#[test]
+fn zippin() {
+    let v = zip(0..5, (0..5).rev()).collect_vec();
+    println!("v = {:?}", v);
+}
+
+#[test]
+fn board_errors_point_into_the_game() {
+    let error = "1,2\n\n1 2\n3 x".parse::<Game>().unwrap_err();
+    assert_eq!((error.line, error.column), (4, 3));
+    let error = "1,2\n\n1 2\n3 4".parse::<Game>().unwrap_err();
+    assert_eq!(error.expected, "5 rows of 5 numbers");
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+enum Mark<T> {
+    Marked(T),
+    Unmarked(T),
+}
+impl<T> Mark<T> {
+    fn unmarked(t: T) -> Self {
+        Mark::Unmarked(t)
+    }
+    fn into_inner(self) -> T {
+        match self {
+            Mark::Marked(t) => t,
+            Mark::Unmarked(t) => t,
+        }
+    }
+
+    /// Returns `true` if the mark is [`Marked`].
+    ///
+    /// [`Marked`]: Mark::Marked
+    fn is_marked(&self) -> bool {
+        matches!(self, Self::Marked(..))
+    }
+}
+
+impl<T: Clone> Mark<T> {
+    fn mark(&mut self) {
+        let t = self.clone().into_inner();
+        *self = Mark::Marked(t)
+    }
+}
+
+impl<T> Deref for Mark<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Mark::Marked(t) => t,
+            Mark::Unmarked(t) => t,
+        }
+    }
+}
+
+impl<T> DerefMut for Mark<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Mark::Marked(t) => t,
+            Mark::Unmarked(t) => t,
+        }
+    }
+}
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 4));
+static PARSED: LazyLock<Game> = LazyLock::new(|| INPUT.parse().unwrap());
+
+fn do_part1(game: &Game) -> anyhow::Result<usize> {
+    let (winner, draw) = game.clone().next().context("Game has no winner")?;
+    let checksum = winner.sum_unmarked() * draw as usize;
+    Ok(checksum)
+}
+fn do_part2(game: &Game) -> anyhow::Result<usize> {
+    let (winner, draw) = game.clone().last().context("Game has no winner")?;
+    let checksum = winner.sum_unmarked() * draw as usize;
+    Ok(checksum)
+}
+
+pub struct GiantSquid;
+
+impl Solution for GiantSquid {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 4;
+    const NAME: &'static str = "giant_squid";
+    const STARS: u8 = 2;
+    const RATING: Option<Rating> = Some(Rating::Average);
+
+    type Parsed = Game;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+}
+
+/// Every board in a grid, with marked numbers lit up, in green once the board has won
+/// Boards are laid out in a square, `across` boards wide
+fn layout(boards: usize) -> (usize, usize) {
+    let across = (boards as f64).sqrt().ceil().max(1.0) as usize;
+    (across, boards.div_ceil(across))
+}
+
+/// A board and the gap before the next
+const SPACING: usize = 6;
+
+fn draw_boards(game: &Game) -> Image {
+    let (across, down) = layout(game.boards.len());
+    let mut image = Image::new(across * SPACING + 1, down * SPACING + 1, Rgb::BLACK);
+    for (index, board) in game.boards.iter().enumerate() {
+        let (top, left) = (1 + index / across * SPACING, 1 + index % across * SPACING);
+        let colour = match board.winner() {
+            true => Rgb(0, 200, 0),
+            false => Rgb::WHITE,
+        };
+        for ((row, column), mark) in board.array.indexed_iter() {
+            let colour = match mark.is_marked() {
+                true => colour,
+                false => Rgb(60, 60, 60),
+            };
+            image.overlay([(top + row, left + column)], colour, 1.0);
+        }
+    }
+    image
+}
+
+/// One frame per draw, until every board has won
+fn record_boards(input: &str) -> anyhow::Result<Animation> {
+    let mut game = input.parse::<Game>()?;
+    let (across, down) = layout(game.boards.len());
+    ensure!(
+        (across * SPACING + 1) * (down * SPACING + 1) <= MAX_PIXELS,
+        "{} boards are too many to draw",
+        game.boards.len()
+    );
+    let mut recorder = Recorder::new(draw_boards);
+    recorder.record(&game)?;
+    while !game.boards.iter().all(Board::winner) && game.draw_next() {
+        recorder.record(&game)?;
+    }
+    Ok(recorder.finish())
+}
+
+pub(crate) static RECORDINGS: [Recording; 1] = [Recording {
+    year: 2021,
+    day: 4,
+    name: "boards",
+    record: record_boards,
+}];
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 27027,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 36975
+}
@@ -0,0 +1,244 @@
+//! # Next time
+//! - Better integration with geo crate etc
+//! - More efficient lattice points iterator
+use crate::{
+    input,
+    parse::{from_token, lines_from, lines_of, ParseError},
+    solution::{Rating, Solution},
+    utils::{CountOccurences, Grid},
+    visualize::{ColourMap, Image, Picture, MAX_PIXELS},
+};
+use anyhow::ensure;
+use itertools::{process_results, Itertools};
+use num::integer::gcd;
+use std::{collections::HashMap, io::BufRead, str::FromStr, sync::LazyLock};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 5));
+static PARSED: LazyLock<Vec<Segment>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+/// How many segments cover each point.
+/// Segments are counted as they're read, so memory grows with the area covered, not the number of segments.
+fn coverage(
+    segments: impl IntoIterator<Item = anyhow::Result<Segment>>,
+    diagonals: bool,
+) -> anyhow::Result<HashMap<Point, usize>> {
+    process_results(segments, |segments| {
+        segments
+            .filter(|segment| diagonals || segment.is_horizontal() || segment.is_vertical())
+            .flat_map(|s| s.lattice_points())
+            .count_occurences()
+    })
+}
+
+/// Points covered by at least two segments
+fn overlaps(
+    segments: impl IntoIterator<Item = anyhow::Result<Segment>>,
+    diagonals: bool,
+) -> anyhow::Result<usize> {
+    let counts = coverage(segments, diagonals)?;
+    Ok(counts.into_values().filter(|count| *count >= 2).count())
+}
+
+fn do_part1(segments: &[Segment]) -> anyhow::Result<usize> {
+    overlaps(segments.iter().copied().map(Ok), false)
+}
+
+fn do_part2(segments: &[Segment]) -> anyhow::Result<usize> {
+    overlaps(segments.iter().copied().map(Ok), true)
+}
+
+pub struct HydrothermalVenture;
+
+impl Solution for HydrothermalVenture {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 5;
+    const NAME: &'static str = "hydrothermal_venture";
+    const STARS: u8 = 2;
+    const RATING: Option<Rating> = Some(Rating::Good);
+
+    type Parsed = Vec<Segment>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+    fn stream(part: u8, reader: &mut dyn BufRead) -> Option<anyhow::Result<String>> {
+        let diagonals = match part {
+            1 => false,
+            2 => true,
+            _ => return None,
+        };
+        Some(overlaps(segments(reader), diagonals).map(|answer| answer.to_string()))
+    }
+}
+
+/// The ocean floor around the vents, hotter where more of them overlap
+fn draw_vents(segments: &[Segment], diagonals: bool) -> anyhow::Result<Image> {
+    let counts = coverage(segments.iter().copied().map(Ok), diagonals).expect("Infallible");
+    let (min_x, max_x) = counts
+        .keys()
+        .map(|point| point.x)
+        .minmax()
+        .into_option()
+        .unwrap_or_default();
+    let (min_y, max_y) = counts
+        .keys()
+        .map(|point| point.y)
+        .minmax()
+        .into_option()
+        .unwrap_or_default();
+    // Coordinates are parsed from a u16, so the differences are never negative
+    let (rows, columns) = match counts.is_empty() {
+        true => (0, 0),
+        false => ((max_y - min_y) as usize + 1, (max_x - min_x) as usize + 1),
+    };
+    ensure!(
+        rows * columns <= MAX_PIXELS,
+        "The vents span {} by {}, too big to draw",
+        columns,
+        rows
+    );
+    let mut floor = Grid::filled(0, rows, columns);
+    for (Point { x, y }, count) in counts {
+        floor[((y - min_y) as usize, (x - min_x) as usize)] = count;
+    }
+    Ok(Image::from_grid(&floor, ColourMap::Heat))
+}
+
+#[test]
+fn draws_around_the_vents() {
+    let segments = parse("1000,1000 -> 1002,1000\n1001,1000 -> 1001,1001\n").unwrap();
+    let image = draw_vents(&segments, false).unwrap();
+    assert_eq!((image.width(), image.height()), (3, 2));
+
+    let far_apart = parse("0,0 -> 0,0\n65535,65535 -> 65535,65535\n").unwrap();
+    assert!(draw_vents(&far_apart, false).is_err());
+}
+
+pub(crate) static PICTURES: [Picture; 2] = [
+    Picture {
+        year: 2021,
+        day: 5,
+        name: "vents",
+        draw: |input| draw_vents(&parse(input)?, false),
+    },
+    Picture {
+        year: 2021,
+        day: 5,
+        name: "vents-with-diagonals",
+        draw: |input| draw_vents(&parse(input)?, true),
+    },
+];
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 6461,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 18065,
+    part1_streaming: HydrothermalVenture::stream(1, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "6461",
+    part2_streaming: HydrothermalVenture::stream(2, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "18065"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    x1: isize,
+    y1: isize,
+    x2: isize,
+    y2: isize,
+}
+
+/// `x1,y1 -> x2,y2`
+impl FromStr for Segment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::of(s, s, "`x1,y1 -> x2,y2`"))?;
+        let point = |point: &str| {
+            let (x, y) = point
+                .split_once(',')
+                .ok_or_else(|| ParseError::of(s, point, "`x,y`"))?;
+            // Small enough that no segment has an unreasonable number of points
+            Ok::<_, ParseError>((
+                from_token::<u16>(s, x, "an x coordinate")? as isize,
+                from_token::<u16>(s, y, "a y coordinate")? as isize,
+            ))
+        };
+        let (x1, y1) = point(start)?;
+        let (x2, y2) = point(end)?;
+        Ok(Self { x1, y1, x2, y2 })
+    }
+}
+
+#[test]
+fn lattice_points() {
+    let points = |segment: &str| {
+        segment
+            .parse::<Segment>()
+            .unwrap()
+            .lattice_points()
+            .into_iter()
+            .map(|Point { x, y }| (x, y))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(points("1,1 -> 1,1"), [(1, 1)]);
+    assert_eq!(points("3,1 -> 1,1"), [(3, 1), (2, 1), (1, 1)]);
+    assert_eq!(points("0,0 -> 4,2"), [(0, 0), (2, 1), (4, 2)]);
+}
+
+impl Segment {
+    fn is_vertical(&self) -> bool {
+        self.x1 == self.x2
+    }
+    fn is_horizontal(&self) -> bool {
+        self.y1 == self.y2
+    }
+    fn lattice_points(&self) -> Vec<Point> {
+        let dy = self.y2 - self.y1;
+        let dx = self.x2 - self.x1;
+
+        if self.is_vertical() && self.is_horizontal() {
+            return vec![Point {
+                x: self.x1,
+                y: self.y1,
+            }];
+        }
+        if self.is_vertical() {
+            return num::range_step_inclusive(self.y1, self.y2, dy.signum())
+                .map(|y| Point { x: self.x1, y })
+                .collect();
+        }
+
+        let divisor = gcd(dy, dx);
+        let dy = dy / divisor;
+        let dx = dx / divisor;
+
+        num::range_step_inclusive(self.x1, self.x2, dx)
+            .enumerate()
+            .map(|(count, x)| Point {
+                x,
+                y: self.y1 + (dy * count as isize),
+            })
+            .collect()
+    }
+}
+
+/// One segment per line, parsed as it's read
+pub fn segments(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<Segment>> {
+    lines_from(reader, str::parse)
+}
+
+fn parse(input: &str) -> anyhow::Result<Vec<Segment>> {
+    Ok(lines_of(input, str::parse)?)
+}
@@ -0,0 +1,127 @@
+//! Wrestled with Iterator and IntoIterator here, but they didn't seem to fit really
+use std::sync::LazyLock;
+
+use crate::{
+    animate::{Animation, Recorder, Recording},
+    input,
+    parse::{csv_of, token, ParseError},
+    solution::{Rating, Solution},
+    visualize::{Image, Rgb},
+};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 6));
+static PARSED: LazyLock<LanternFish> = LazyLock::new(|| LanternFish::from_csv(&INPUT).unwrap());
+
+fn do_part1(fish: &LanternFish) -> anyhow::Result<usize> {
+    let mut fish = *fish;
+    for _ in 0..80 {
+        fish = fish.step_simulation()
+    }
+    Ok(fish.total())
+}
+fn do_part2(fish: &LanternFish) -> anyhow::Result<usize> {
+    let mut fish = *fish;
+    for _ in 0..256 {
+        fish = fish.step_simulation()
+    }
+    Ok(fish.total())
+}
+
+pub struct Lanternfish;
+
+impl Solution for Lanternfish {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 6;
+    const NAME: &'static str = "lanternfish";
+    const STARS: u8 = 2;
+    const RATING: Option<Rating> = Some(Rating::Good);
+
+    type Parsed = LanternFish;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(LanternFish::from_csv(input)?)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+}
+
+/// A bar for each timer, scaled to the most common one since the total grows so quickly
+fn draw_timers(fish: &LanternFish) -> Image {
+    const HEIGHT: usize = 40;
+    const WIDTH: usize = 4;
+    let mut image = Image::new(9 * (WIDTH + 1) + 1, HEIGHT, Rgb::BLACK);
+    let most = fish.timers.iter().copied().max().unwrap_or_default().max(1);
+    for (timer, count) in fish.timers.into_iter().enumerate() {
+        let height = (count as f64 / most as f64 * HEIGHT as f64) as usize;
+        let left = 1 + timer * (WIDTH + 1);
+        let bar = (HEIGHT - height..HEIGHT)
+            .flat_map(|row| (left..left + WIDTH).map(move |column| (row, column)));
+        image.overlay(bar, Rgb::distinct(timer), 1.0);
+    }
+    image
+}
+
+/// One frame per day of the simulation, starting from the input
+fn record_timers(input: &str, days: usize) -> anyhow::Result<Animation> {
+    let mut fish = LanternFish::from_csv(input)?;
+    let mut recorder = Recorder::new(draw_timers);
+    recorder.record(&fish)?;
+    for _ in 0..days {
+        fish = fish.step_simulation();
+        recorder.record(&fish)?;
+    }
+    Ok(recorder.finish())
+}
+
+pub(crate) static RECORDINGS: [Recording; 2] = [
+    Recording {
+        year: 2021,
+        day: 6,
+        name: "timers",
+        record: |input| record_timers(input, 80),
+    },
+    Recording {
+        year: 2021,
+        day: 6,
+        name: "timers-256",
+        record: |input| record_timers(input, 256),
+    },
+];
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 390011,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 1746710169834
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LanternFish {
+    timers: [usize; 9],
+}
+
+impl LanternFish {
+    fn total(&self) -> usize {
+        self.timers.into_iter().sum()
+    }
+    fn from_csv(csv: &str) -> Result<Self, ParseError> {
+        let timers = csv_of(csv, |el| match token::<usize>("a timer from 0 to 8")(el)? {
+            timer if timer < 9 => Ok(timer),
+            _ => Err(ParseError::of(el, el, "a timer from 0 to 8")),
+        })?;
+        Ok(timers.into_iter().fold(Self::default(), |mut acc, timer| {
+            acc.timers[timer] += 1;
+            acc
+        }))
+    }
+    fn step_simulation(mut self) -> Self {
+        let create = self.timers[0];
+        self.timers.rotate_left(1);
+        self.timers[6] += create; // day 8 will rotate through
+        self
+    }
+}
@@ -0,0 +1,12 @@
+//! Puzzles from https://adventofcode.com/2021
+pub mod binary_diagnostic;
+pub mod dive;
+mod dumbo_octopus;
+pub mod giant_squid;
+pub mod hydrothermal_venture;
+pub mod lanternfish;
+pub mod seven_segment_search;
+pub mod smoke_basin;
+pub mod sonar_sweep;
+pub mod syntax_scoring;
+pub mod treachery_of_whales;
@@ -0,0 +1,181 @@
+use anyhow::{bail, Context};
+use itertools::Itertools;
+use std::{collections::HashSet, str::FromStr, sync::LazyLock};
+
+use crate::{
+    input,
+    parse::{lines_of, tokens, ParseError},
+    solution::Solution,
+};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 8));
+static PARSED: LazyLock<Vec<Observation>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+#[derive(Debug)]
+pub struct Observation {
+    inputs: Vec<HashSet<char>>,
+    outputs: Vec<HashSet<char>>,
+}
+
+impl Observation {
+    fn decode(&self) -> anyhow::Result<usize> {
+        let mut inputs = self.inputs.clone();
+        let one = remove(&mut inputs, |set| set.len() == 2)?;
+        let four = remove(&mut inputs, |set| set.len() == 4)?;
+
+        let res = self
+            .outputs
+            .iter()
+            .map(|d| {
+                let digit = match d.len() {
+                    2 => '1',
+                    3 => '7',
+                    4 => '4',
+                    7 => '8',
+                    5 => {
+                        if d.is_superset(&one) {
+                            '3'
+                        } else {
+                            match d.intersection(&four).count() {
+                                2 => '2',
+                                3 => '5',
+                                _ => {
+                                    bail!("Can't decode {:?}, one: {:?}, four: {:?}", d, one, four)
+                                }
+                            }
+                        }
+                    }
+                    6 => {
+                        if d.is_superset(&four) {
+                            '9'
+                        } else if d.is_superset(&one) {
+                            '0'
+                        } else {
+                            '6'
+                        }
+                    }
+                    other => bail!("No digit has {} segments: {:?}", other, d),
+                };
+                Ok(digit)
+            })
+            .collect::<anyhow::Result<String>>()?;
+
+        Ok(res.parse()?)
+    }
+}
+
+fn remove<T>(v: &mut Vec<T>, mut predicate: impl FnMut(&T) -> bool) -> anyhow::Result<T> {
+    let position = v
+        .iter()
+        .find_position(|t| predicate(*t))
+        .context("Not found")?
+        .0;
+    Ok(v.remove(position))
+}
+
+impl FromStr for Observation {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some((i, c)) = value
+            .char_indices()
+            .find(|(_, c)| !matches!(c, 'a'..='g' | ' ' | '|'))
+        {
+            return Err(ParseError::of(
+                value,
+                &value[i..i + c.len_utf8()],
+                "a segment from a to g",
+            ));
+        }
+        let (inputs, outputs) = match tokens(value, "|").collect_vec()[..] {
+            [inputs, outputs] => (inputs, outputs),
+            [_, _, extra, ..] => return Err(ParseError::of(value, extra, "the end of the line")),
+            _ => return Err(ParseError::end(value, "`|`")),
+        };
+        let patterns = |part: &str, count: usize, expected: &str| {
+            let patterns = tokens(part, " ")
+                .map(|s| HashSet::from_iter(s.chars()))
+                .collect_vec();
+            match patterns.len() == count {
+                true => Ok(patterns),
+                false => Err(ParseError::of(value, part, expected)),
+            }
+        };
+        Ok(Self {
+            inputs: patterns(inputs, 10, "10 signal patterns")?,
+            outputs: patterns(outputs, 4, "4 output digits")?,
+        })
+    }
+}
+
+fn parse(input: &str) -> anyhow::Result<Vec<Observation>> {
+    Ok(lines_of(input, str::parse)?)
+}
+
+#[test]
+fn observation_errors() {
+    let patterns = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb";
+    let error = format!("{} | a b c d | e", patterns)
+        .parse::<Observation>()
+        .unwrap_err();
+    assert_eq!(
+        (error.expected.as_str(), error.text.as_str()),
+        ("the end of the line", "e")
+    );
+    let error = patterns.parse::<Observation>().unwrap_err();
+    assert_eq!(error.expected, "`|`");
+    let error = format!("{} | a b c", patterns)
+        .parse::<Observation>()
+        .unwrap_err();
+    assert_eq!(
+        (error.expected.as_str(), error.column),
+        ("4 output digits", 62)
+    );
+}
+
+fn do_part1(observations: &[Observation]) -> anyhow::Result<usize> {
+    let c = observations
+        .iter()
+        .flat_map(|o| &o.outputs)
+        .filter(|o| matches!(o.len(), 2 | 4 | 3 | 7))
+        .count();
+    Ok(c)
+}
+fn do_part2(observations: &[Observation]) -> anyhow::Result<usize> {
+    let res = observations
+        .iter()
+        .map(Observation::decode)
+        .collect::<Result<Vec<_>, _>>()?
+        .iter()
+        .sum();
+
+    Ok(res)
+}
+
+pub struct SevenSegmentSearch;
+
+impl Solution for SevenSegmentSearch {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 8;
+    const NAME: &'static str = "seven_segment_search";
+    const STARS: u8 = 2;
+
+    type Parsed = Vec<Observation>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+}
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 412,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 978171
+}
@@ -0,0 +1,130 @@
+//! This is a mess, I got lazy
+use std::sync::LazyLock;
+
+use anyhow::Context;
+
+use crate::{
+    input,
+    parse::{digit_grid, ParseError},
+    search::{self, GridGraph},
+    solution::Solution,
+    utils::{Grid, Neighbourhood, Neighbouring},
+    visualize::{ColourMap, Image, Picture, Rgb},
+};
+
+fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+    digit_grid(input, "a height from 0 to 9")
+}
+
+trait HeightMap {
+    fn is_low(&self, row: usize, column: usize) -> bool;
+    fn flood_fill(&self, row: usize, column: usize) -> Vec<(usize, usize)>;
+}
+
+impl HeightMap for Grid<u32> {
+    /// Lower than everything up, down, left and right of it
+    fn is_low(&self, row: usize, column: usize) -> bool {
+        let height = self[(row, column)];
+        self.neighbours(row, column, Neighbourhood::ORTHOGONAL)
+            .expect("Valid index")
+            .all(|(_, _, neighbour)| *neighbour > height)
+    }
+
+    /// Everything that flows down to `(row, column)`, up to the walls of 9
+    fn flood_fill(&self, row: usize, column: usize) -> Vec<(usize, usize)> {
+        let graph = GridGraph {
+            grid: self,
+            neighbourhood: Neighbourhood::ORTHOGONAL,
+            cost: |_: &u32, to: &u32| (*to < 9).then_some(1),
+        };
+        search::dfs(&graph, (row, column), |_| false).order
+    }
+}
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 9));
+static PARSED: LazyLock<Grid<u32>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+fn do_part1(height_map: &Grid<u32>) -> anyhow::Result<u32> {
+    let total_risk_level = low_points(height_map)
+        .map(|(row, column)| height_map[(row, column)] + 1)
+        .sum();
+    Ok(total_risk_level)
+}
+
+/// `(row, column)` of every point lower than all its neighbours
+fn low_points(height_map: &Grid<u32>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    height_map
+        .positions()
+        .filter(|&(row, column)| height_map.is_low(row, column))
+}
+
+fn do_part2(height_map: &Grid<u32>) -> anyhow::Result<usize> {
+    let mut basin_sizes = low_points(height_map)
+        .map(|(row, column)| height_map.flood_fill(row, column).len())
+        .collect::<Vec<_>>();
+    basin_sizes.sort();
+    let top3 = basin_sizes
+        .into_iter()
+        .rev()
+        .take(3)
+        .try_fold(1usize, |a, b| a.checked_mul(b))
+        .context("Basin sizes overflowed")?;
+    Ok(top3)
+}
+
+pub struct SmokeBasin;
+
+impl Solution for SmokeBasin {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 9;
+    const NAME: &'static str = "smoke_basin";
+    const STARS: u8 = 2;
+
+    type Parsed = Grid<u32>;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        Ok(parse(input)?)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+}
+
+/// Heights, with each basin tinted its own colour and the low points in red
+fn draw_basins(height_map: &Grid<u32>) -> Image {
+    let mut image = Image::from_grid(height_map, ColourMap::Terrain);
+    for (index, (row, column)) in low_points(height_map).enumerate() {
+        image.overlay(
+            height_map.flood_fill(row, column),
+            Rgb::distinct(index),
+            0.5,
+        );
+    }
+    image.overlay(low_points(height_map), Rgb::RED, 1.0);
+    image
+}
+
+pub(crate) static PICTURES: [Picture; 2] = [
+    Picture {
+        year: 2021,
+        day: 9,
+        name: "basins",
+        draw: |input| Ok(draw_basins(&parse(input)?)),
+    },
+    Picture {
+        year: 2021,
+        day: 9,
+        name: "heights",
+        draw: |input| Ok(Image::from_grid(&parse(input)?, ColourMap::Terrain)),
+    },
+];
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 478,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 1327014
+}
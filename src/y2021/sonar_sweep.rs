@@ -0,0 +1,92 @@
+use std::{collections::VecDeque, io::BufRead, sync::LazyLock};
+
+use crate::{
+    input,
+    parse::{lines_from, lines_of, token},
+    solution::{Rating, Solution},
+};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 1));
+static PARSED: LazyLock<Vec<u32>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+/// One depth per line, parsed as it's read
+pub fn depths(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<u32>> {
+    lines_from(reader, token("a depth"))
+}
+
+fn parse(input: &str) -> anyhow::Result<Vec<u32>> {
+    Ok(lines_of(input, token("a depth"))?)
+}
+
+/// How many depths are deeper than the one `gap` before.
+/// Consecutive windows share all but their ends, so comparing sums of windows of `gap` is the same thing,
+/// and only the last `gap` depths need to be kept.
+fn increases(
+    depths: impl IntoIterator<Item = anyhow::Result<u32>>,
+    gap: usize,
+) -> anyhow::Result<usize> {
+    let mut previous = VecDeque::with_capacity(gap);
+    let mut count = 0;
+    for depth in depths {
+        let depth = depth?;
+        if previous.len() == gap && previous.pop_front().is_some_and(|earlier| depth > earlier) {
+            count += 1;
+        }
+        previous.push_back(depth);
+    }
+    Ok(count)
+}
+
+fn do_part1(depths: &[u32]) -> anyhow::Result<usize> {
+    increases(depths.iter().copied().map(Ok), 1)
+}
+fn do_part2(depths: &[u32]) -> anyhow::Result<usize> {
+    increases(depths.iter().copied().map(Ok), 3)
+}
+
+pub struct SonarSweep;
+
+impl Solution for SonarSweep {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 1;
+    const NAME: &'static str = "sonar_sweep";
+    const STARS: u8 = 2;
+    const RATING: Option<Rating> = Some(Rating::Interesting);
+
+    type Parsed = Vec<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+    fn stream(part: u8, reader: &mut dyn BufRead) -> Option<anyhow::Result<String>> {
+        let gap = match part {
+            1 => 1,
+            2 => 3,
+            _ => return None,
+        };
+        Some(increases(depths(reader), gap).map(|answer| answer.to_string()))
+    }
+}
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 1316,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 1344,
+    part1_streaming: SonarSweep::stream(1, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "1316",
+    part2_streaming: SonarSweep::stream(2, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "1344"
+}
+
+#[test]
+fn windows_of_three() {
+    // The worked example
+    let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+    assert_eq!(do_part1(&depths).unwrap(), 7);
+    assert_eq!(do_part2(&depths).unwrap(), 5);
+}
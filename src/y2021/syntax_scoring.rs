@@ -0,0 +1,149 @@
+use std::{borrow::Borrow, io::BufRead, str::FromStr, sync::LazyLock};
+
+use anyhow::Context;
+use itertools::process_results;
+
+use crate::{
+    input,
+    parse::{lines_from, lines_of, ParseError},
+    solution::Solution,
+    y2021::treachery_of_whales::median,
+};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 10));
+static PARSED: LazyLock<Vec<Line>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+#[derive(Debug)]
+pub enum Line {
+    Illegal(char),
+    /// The brackets left open, innermost last
+    Incomplete(Vec<char>),
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stack = Vec::new();
+        for (i, char) in s.char_indices() {
+            match char {
+                '(' | '[' | '{' | '<' => stack.push(char),
+                ')' => match stack.pop() {
+                    Some('(') => (),
+                    Some(_) | None => return Ok(Line::Illegal(char)),
+                },
+                ']' => match stack.pop() {
+                    Some('[') => (),
+                    Some(_) | None => return Ok(Line::Illegal(char)),
+                },
+                '}' => match stack.pop() {
+                    Some('{') => (),
+                    Some(_) | None => return Ok(Line::Illegal(char)),
+                },
+                '>' => match stack.pop() {
+                    Some('<') => (),
+                    Some(_) | None => return Ok(Line::Illegal(char)),
+                },
+                _ => return Err(ParseError::of(s, &s[i..i + char.len_utf8()], "a bracket")),
+            }
+        }
+        Ok(Line::Incomplete(stack))
+    }
+}
+
+/// `None` if there are too many brackets to close for the score to fit
+fn completion_score(open: &[char]) -> Option<usize> {
+    open.iter().rev().try_fold(0usize, |score, opening| {
+        let points = match opening {
+            '(' => 1,
+            '[' => 2,
+            '{' => 3,
+            _ => 4,
+        };
+        score.checked_mul(5)?.checked_add(points)
+    })
+}
+
+/// Each line is checked as it's read
+pub fn lines(reader: impl BufRead) -> impl Iterator<Item = anyhow::Result<Line>> {
+    lines_from(reader, str::parse)
+}
+
+fn parse(input: &str) -> anyhow::Result<Vec<Line>> {
+    Ok(lines_of(input, str::parse)?)
+}
+
+fn syntax_error_score(line: &Line) -> usize {
+    match line {
+        Line::Illegal(closing) => match closing {
+            ')' => 3,
+            ']' => 57,
+            '}' => 1197,
+            '>' => 25137,
+            _ => unreachable!(),
+        },
+        Line::Incomplete(_) => 0,
+    }
+}
+
+/// Only incomplete lines are scored, and only their scores are kept
+fn middle_completion_score(
+    lines: impl Iterator<Item = impl Borrow<Line>>,
+) -> anyhow::Result<usize> {
+    let mut scores = lines
+        .filter_map(|line| match line.borrow() {
+            Line::Illegal(_) => None,
+            Line::Incomplete(open) => Some(completion_score(open)),
+        })
+        .collect::<Option<Vec<_>>>()
+        .context("Completion score overflowed")?;
+    Ok(*median(&mut scores).context("No median")?)
+}
+
+fn do_part1(lines: &[Line]) -> anyhow::Result<usize> {
+    Ok(lines.iter().map(syntax_error_score).sum())
+}
+fn do_part2(lines: &[Line]) -> anyhow::Result<usize> {
+    middle_completion_score(lines.iter())
+}
+
+pub struct SyntaxScoring;
+
+impl Solution for SyntaxScoring {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 10;
+    const NAME: &'static str = "syntax_scoring";
+    const STARS: u8 = 2;
+
+    type Parsed = Vec<Line>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+    fn stream(part: u8, reader: &mut dyn BufRead) -> Option<anyhow::Result<String>> {
+        let answer = match part {
+            1 => process_results(lines(reader), |lines| {
+                lines.map(|line| syntax_error_score(&line)).sum()
+            }),
+            2 => process_results(lines(reader), |lines| middle_completion_score(lines))
+                .and_then(|score| score),
+            _ => return None,
+        };
+        Some(answer.map(|answer: usize| answer.to_string()))
+    }
+}
+
+crate::utils::benchtest! {
+    part1: do_part1(black_box(&PARSED)).unwrap() => 243939,
+    part2: do_part2(black_box(&PARSED)).unwrap() => 2421222841,
+    part1_streaming: SyntaxScoring::stream(1, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "243939",
+    part2_streaming: SyntaxScoring::stream(2, &mut black_box(INPUT.as_bytes())).unwrap().unwrap() => "2421222841"
+}
@@ -0,0 +1,147 @@
+use std::{cmp::min, sync::LazyLock};
+
+use anyhow::Context;
+use num::Num;
+
+use crate::{
+    differential::{Implementation, Implementations},
+    input,
+    parse::{csv_of, token},
+    solution::Solution,
+};
+
+static INPUT: LazyLock<String> = LazyLock::new(|| input::puzzle_input(2021, 7));
+static PARSED: LazyLock<Vec<usize>> = LazyLock::new(|| parse(&INPUT).unwrap());
+
+fn parse(input: &str) -> anyhow::Result<Vec<usize>> {
+    // Part 1 tries every position up to the furthest crab
+    let positions = csv_of(input, token::<u16>("a position"))?;
+    Ok(positions.into_iter().map(usize::from).collect())
+}
+
+fn do_part1(positions: &[usize]) -> anyhow::Result<usize> {
+    let max = positions.iter().max().context("Must have one input")?;
+    let min_cost = (0..=*max)
+        .map(|destination| {
+            positions.iter().fold(0, |total, source| {
+                let distance: isize = *source as isize - destination as isize;
+                total + distance.abs() // abs_diff actually takes twice as long
+            })
+        })
+        .min()
+        .context("Must have one input")?;
+    Ok(min_cost as usize)
+}
+
+fn do_part2(positions: &[usize]) -> anyhow::Result<usize> {
+    let max = positions.iter().max().context("Must have one input")?;
+    let min_cost = (0..=*max)
+        .map(|destination| {
+            positions.iter().fold(0, |total, source| {
+                let distance: isize = *source as isize - destination as isize;
+                let distance = distance.abs();
+                let cost = (distance * (distance + 1)) / 2;
+                total + cost
+            })
+        })
+        .min()
+        .context("Must have one input")?;
+    Ok(min_cost as usize)
+}
+
+pub struct TreacheryOfWhales;
+
+impl Solution for TreacheryOfWhales {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 7;
+    const NAME: &'static str = "treachery_of_whales";
+    const STARS: u8 = 2;
+
+    type Parsed = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed> {
+        parse(input)
+    }
+    fn part1(parsed: &Self::Parsed) -> anyhow::Result<Self::Part1> {
+        do_part1(parsed)
+    }
+    fn part2(parsed: &Self::Parsed) -> anyhow::Result<Self::Part2> {
+        do_part2(parsed)
+    }
+}
+
+pub(crate) static IMPLEMENTATIONS: Implementations = Implementations {
+    year: 2021,
+    day: 7,
+    implementations: &[
+        Implementation {
+            part: 1,
+            name: "brute force",
+            solve: |input| do_part1(&parse(input)?).map(|answer| answer.to_string()),
+        },
+        Implementation {
+            part: 1,
+            name: "median",
+            solve: |input| optimized(&parse(input)?).map(|(part1, _)| part1.to_string()),
+        },
+        Implementation {
+            part: 2,
+            name: "brute force",
+            solve: |input| do_part2(&parse(input)?).map(|answer| answer.to_string()),
+        },
+        Implementation {
+            part: 2,
+            name: "mean",
+            solve: |input| optimized(&parse(input)?).map(|(_, part2)| part2.to_string()),
+        },
+    ],
+};
+
+crate::utils::benchtest! {
+    part1_brute: do_part1(black_box(&PARSED)).unwrap() => 323647,
+
+    part2_brute: do_part2(black_box(&PARSED)).unwrap() => 87640209,
+    both_optimized: optimized(black_box(&PARSED)).unwrap() => (323647, 87640209)
+}
+
+fn optimized(positions: &[usize]) -> anyhow::Result<(usize, usize)> {
+    let mut positions = positions.to_vec();
+    let median = *median(&mut positions).context("Empty")?; // position part 1
+    let mean_lower = mean(&positions); // position part 2
+    let mean_upper = mean_lower + 1;
+
+    let mut part1 = 0;
+    let mut part2_lower = 0;
+    let mut part2_upper = 0;
+
+    for position in positions {
+        part1 += position.abs_diff(median);
+        part2_lower += gaussian_sum(position.abs_diff(mean_upper));
+        part2_upper += gaussian_sum(position.abs_diff(mean_lower));
+    }
+
+    Ok((part1, min(part2_lower, part2_upper)))
+}
+
+fn gaussian_sum(to: usize) -> usize {
+    // n(n+1)
+    //   2
+    (to * (to + 1)) / 2
+}
+
+fn mean<T: Num + Copy>(of: &[T]) -> T {
+    let (total, length) = of
+        .iter()
+        .fold((T::zero(), T::zero()), |(total, length), el| {
+            (total + *el, length + T::one())
+        });
+    total / length
+}
+
+pub fn median<T: Num + Ord>(of: &mut [T]) -> Option<&T> {
+    of.sort_unstable();
+    let halfway = of.len() / 2;
+    of.get(halfway)
+}
@@ -0,0 +1,38 @@
+//! One test per part of every registered day, checking the worked example from the puzzle.
+//! Parts without an expected answer yet are ignored, a missing fixture is a failure.
+use std::path::Path;
+
+use advent_of_code::{fixture::Fixture, registry};
+use libtest_mimic::{Arguments, Failed, Trial};
+
+fn main() {
+    let args = Arguments::from_args();
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/inputs");
+    let trials = registry::all()
+        .flat_map(|solver| {
+            let name = format!(
+                "{}::day{:02}::{}",
+                solver.year(),
+                solver.day(),
+                solver.name()
+            );
+            let path = Fixture::path_in(&directory, solver.year(), solver.day());
+            match Fixture::load(&path) {
+                Ok(fixture) => (1..=2)
+                    .map(|part| {
+                        let fixture = fixture.clone();
+                        let ignored = fixture.answer(part).is_none();
+                        Trial::test(format!("{}::part{}", name, part), move || {
+                            fixture.check(solver, part).map_err(Failed::from)
+                        })
+                        .with_ignored_flag(ignored)
+                    })
+                    .collect(),
+                Err(error) => vec![Trial::test(format!("{}::fixture", name), move || {
+                    Err(Failed::from(format!("{:#}", error)))
+                })],
+            }
+        })
+        .collect();
+    libtest_mimic::run(&args, trials).exit();
+}
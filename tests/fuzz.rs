@@ -0,0 +1,68 @@
+//! Fuzz every registered day's parser, starting from the real inputs and the worked examples.
+//! Set `AOC_FUZZ_ITERATIONS` to run for longer, and `AOC_FUZZ_SEED` to reproduce a failure.
+//!
+//! Anything the fuzzer finds belongs in `tests/regressions/<year>-<day>-<name>.txt`,
+//! which is run through the parser and both parts on every test run.
+use std::{env, fs, path::Path, time::Duration};
+
+use advent_of_code::{fuzz, input::InputSource, registry};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn var(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+#[test]
+fn parsers_survive_fuzzing() {
+    let iterations = var("AOC_FUZZ_ITERATIONS", 200) as usize;
+    let seed = var("AOC_FUZZ_SEED", 2021);
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/inputs");
+    let inputs = InputSource::default();
+    let mut failures = Vec::new();
+    for solver in registry::all() {
+        // Each day gets its own stream, so adding a day doesn't change what the others see
+        let mut rng = ChaCha8Rng::seed_from_u64(
+            seed ^ u64::from(solver.year()) << 8 ^ u64::from(solver.day()),
+        );
+        let seeds = fuzz::seeds(solver, &inputs, &fixtures);
+        if let Some(finding) = fuzz::fuzz(solver, &seeds, iterations, TIMEOUT, &mut rng) {
+            failures.push(format!(
+                "{} day {} {} on {:?} (AOC_FUZZ_SEED={})",
+                solver.year(),
+                solver.day(),
+                finding.failure,
+                finding.input,
+                seed
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn regressions() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/regressions");
+    let mut failures = Vec::new();
+    for entry in fs::read_dir(&directory).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let mut parts = name.splitn(3, '-');
+        let (year, day) = match (parts.next(), parts.next()) {
+            (Some(year), Some(day)) => (year.parse().unwrap(), day.parse().unwrap()),
+            _ => panic!("{} should be named <year>-<day>-<name>.txt", path.display()),
+        };
+        let solver = registry::find(year, day)
+            .unwrap_or_else(|| panic!("{} isn't for a registered day", path.display()));
+        let input = fs::read_to_string(&path).unwrap();
+        if let Err(failure) = fuzz::check_solver(solver, &input, TIMEOUT) {
+            failures.push(format!("{} {}", name, failure));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,85 @@
+//! Every registered day should reject bad input with an error, never a panic or a hang.
+//! The inputs are generic junk, plus mangled versions of each day's example.
+use std::{path::Path, time::Duration};
+
+use advent_of_code::{fixture::Fixture, fuzz, registry};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+const JUNK: &[&str] = &[
+    "",
+    "\n",
+    "\n\n\n",
+    " ",
+    "0",
+    "-1",
+    "1,2,3",
+    "0,0",
+    "99999999999999999999999999",
+    "18446744073709551615\n18446744073709551615\n18446744073709551615\n18446744073709551615",
+    "a",
+    "🦑",
+    "\u{0}",
+    "0\r\n1\r\n",
+    "0,0 -> 0,0",
+    "0,0 -> 65535,65535\n65535,0 -> 0,65535",
+    "up 1",
+    "forward 18446744073709551615\ndown 18446744073709551615\nforward 2",
+    "0\n00\n000",
+    "1111111111111111111111111111111111111111111111111111111111111111111111",
+    "abcdefg | abcdefg",
+    "a b c d e f g ab abc abcd | a b c d",
+    "65535",
+    "9\n99\n",
+    "(((((((((((((((((((((((((((((((((((((((((((((((((((",
+    "<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<",
+];
+
+/// Replacements for single characters of an example
+const MANGLES: &[&str] = &["", "0", "9", "x", " ", "\n", ",", "|", "-", "("];
+
+fn mangled(example: &str) -> Vec<String> {
+    let mut inputs = Vec::new();
+    for (i, c) in example.char_indices() {
+        // Truncate at every line
+        if c == '\n' {
+            inputs.push(String::from(&example[..i]));
+        }
+        for mangle in MANGLES {
+            inputs.push(format!(
+                "{}{}{}",
+                &example[..i],
+                mangle,
+                &example[i + c.len_utf8()..]
+            ));
+        }
+    }
+    inputs.push(example.repeat(2));
+    inputs
+}
+
+#[test]
+fn solvers_never_panic() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/inputs");
+    let mut failures = Vec::new();
+    for solver in registry::all() {
+        let mut inputs = JUNK.iter().copied().map(String::from).collect::<Vec<_>>();
+        if let Ok(fixture) =
+            Fixture::load(&Fixture::path_in(&directory, solver.year(), solver.day()))
+        {
+            inputs.extend(mangled(&fixture.input));
+        }
+        for input in inputs {
+            if let Err(failure) = fuzz::check_solver(solver, &input, TIMEOUT) {
+                failures.push(format!(
+                    "{} day {} {} on {:?}",
+                    solver.year(),
+                    solver.day(),
+                    failure,
+                    input
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}